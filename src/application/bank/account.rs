@@ -49,15 +49,15 @@ mod tests {
         let acc_storage = MockAccStorage::new();
         let address = Address {
             network: "lokichain".to_string(),
-            vk: VerifyKey([1; 32])
+            vk: VerifyKey::Ed25519([1; 32])
         };
         let account = Account {
             address: address.clone(),
             nonce: 1,
-            balance: Token {
+            balances: Token {
                 value: 100,
                 denom: "LOKI".to_string()
-            }
+            }.into()
         };
         acc_storage.set(address.clone(), account.clone()).await;
 
@@ -73,7 +73,7 @@ mod tests {
     async fn test_get_account_acc_not_found() {
         let address = Address {
             network: "lokichain".to_string(),
-            vk: VerifyKey([1; 32])
+            vk: VerifyKey::Ed25519([1; 32])
         };
 
         let interactor = GetAccount {
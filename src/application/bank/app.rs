@@ -0,0 +1,719 @@
+use crate::application::common::app::{decode_payload, App};
+use crate::application::common::config::NodeConfig;
+use crate::application::common::context::{ExecutionContext, Stores};
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::account::{decimal_balances, Balances};
+use crate::domain::models::address::Address;
+use crate::domain::models::allowance::Allowance;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::receipt::Event;
+use crate::domain::models::token::{decimal_token, Token};
+use crate::domain::models::transaction::Transaction;
+use crate::domain::models::validity::{ValidityBound, ValidityWindow};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Payload of `bank`/`transfer`, the amount is `[Transaction::amount]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferPayload {
+    pub receiver: Address
+}
+
+/// Single payment of `bank`/`multi_send`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Output {
+    pub receiver: Address,
+    #[serde(with = "decimal_token")]
+    pub amount: Token
+}
+
+/// Payload of `bank`/`multi_send`, `[Transaction::amount]` is neither moved nor checked
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSendPayload {
+    pub outputs: Vec<Output>
+}
+
+/// Payload of `bank`/`approve` and `bank`/`increase_allowance`, `[Transaction::amount]` is neither moved nor checked
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovePayload {
+    pub spender: Address,
+    #[serde(with = "decimal_token")]
+    pub amount: Token,
+    /// last block the allowance can be spent in, never expires if not set
+    #[serde(default)]
+    pub expiry: Option<ValidityBound>
+}
+
+/// Payload of `bank`/`revoke`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevokePayload {
+    pub spender: Address,
+    pub denom: String
+}
+
+/// Payload of `bank`/`transfer_from`, the amount is `[Transaction::amount]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferFromPayload {
+    pub owner: Address,
+    pub receiver: Address
+}
+
+/// Payload of the `allowance` query
+#[derive(Clone, Debug, Deserialize)]
+pub struct AllowanceQuery {
+    pub owner: Address,
+    pub spender: Address,
+    pub denom: String
+}
+
+/// Payload of the `balances` query
+#[derive(Clone, Debug, Deserialize)]
+pub struct BalancesQuery {
+    pub address: Address
+}
+
+/// Response of the `balances` query, a `denom -> value` map with values as decimal strings
+#[derive(Clone, Debug, Serialize)]
+pub struct BalancesResponse(#[serde(with = "decimal_balances")] pub Balances);
+
+/// Moves tokens between accounts
+///
+/// Operations:
+/// - `transfer`: debits `[Transaction::amount]` from the sender and credits it to the receiver,
+///   the receiver account is created by its first transfer
+/// - `multi_send`: pays every output from the sender, possibly in several denoms.
+///   The sender must hold the total of every denom, otherwise nothing is paid.
+///   Every output is charged a read and a write of its receiver
+/// - `approve`: sets the allowance of a spender over a denom of the sender, replacing the previous one
+/// - `increase_allowance`: adds to the allowance, an expired one starts over.
+///   The expiry is kept if the payload does not set one
+/// - `revoke`: removes the allowance of a spender over a denom of the sender
+/// - `transfer_from`: the sender spends `[Transaction::amount]` of the allowance the owner gave it,
+///   paying the receiver from the owner
+///
+/// Queries:
+/// - `balances`: balances of an address, empty for an unknown one
+/// - `allowance`: allowance of an owner, a spender and a denom, expired ones included
+pub struct BankApp<'a> {
+    pub config: &'a NodeConfig
+}
+
+impl BankApp<'_> {
+    async fn transfer(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: TransferPayload = decode_payload(&transaction.data.payload)?;
+        ctx.withdraw(&transaction.sender, &transaction.amount).await?;
+        ctx.deposit(&payload.receiver, &transaction.amount).await?;
+        ctx.emit(transfer_event(&transaction.sender, &payload.receiver, &transaction.amount));
+        Ok(())
+    }
+
+    async fn multi_send(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: MultiSendPayload = decode_payload(&transaction.data.payload)?;
+        let total = self.multi_send_total(&payload)?;
+
+        let balances = ctx.account(&transaction.sender).await?
+            .map(|acc| acc.balances)
+            .unwrap_or_default();
+        if total.iter().any(|token| balances.get(&token.denom) < token) {
+            return Err(ApplicationError::InvalidData(
+                [("body.data.payload.outputs".to_string(), "insufficient funds".to_string())].into()
+            ));
+        }
+
+        for token in total.iter() {
+            ctx.withdraw(&transaction.sender, &token).await?;
+        }
+        for output in &payload.outputs {
+            ctx.deposit(&output.receiver, &output.amount).await?;
+            ctx.emit(transfer_event(&transaction.sender, &output.receiver, &output.amount));
+        }
+        Ok(())
+    }
+
+    async fn approve(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: ApprovePayload = decode_payload(&transaction.data.payload)?;
+        self.check_spender(transaction, &payload, ctx)?;
+        let allowance = Allowance {
+            owner: transaction.sender.clone(),
+            spender: payload.spender,
+            amount: payload.amount,
+            expiry: payload.expiry
+        };
+        ctx.set_state(&allowance_key(&allowance.owner, &allowance.spender, &allowance.amount.denom), &allowance)?;
+        ctx.emit(allowance_event("approve", &allowance));
+        Ok(())
+    }
+
+    async fn increase_allowance(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: ApprovePayload = decode_payload(&transaction.data.payload)?;
+        self.check_spender(transaction, &payload, ctx)?;
+        let key = allowance_key(&transaction.sender, &payload.spender, &payload.amount.denom);
+        let allowance = match ctx.state::<Allowance>(&key).await? {
+            Some(allowance) if !allowance.is_expired(ctx.height, ctx.timestamp) => Allowance {
+                amount: allowance.amount.checked_add(&payload.amount)?,
+                expiry: payload.expiry.or(allowance.expiry),
+                ..allowance
+            },
+            _ => Allowance {
+                owner: transaction.sender.clone(),
+                spender: payload.spender,
+                amount: payload.amount,
+                expiry: payload.expiry
+            },
+        };
+        ctx.set_state(&key, &allowance)?;
+        ctx.emit(allowance_event("increase_allowance", &allowance));
+        Ok(())
+    }
+
+    async fn revoke(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: RevokePayload = decode_payload(&transaction.data.payload)?;
+        let key = allowance_key(&transaction.sender, &payload.spender, &payload.denom);
+        if ctx.state::<Allowance>(&key).await?.is_none() {
+            return Err(ApplicationError::NotFound(format!("allowance of {} over {}", payload.spender, payload.denom)));
+        }
+        ctx.remove_state(&key)?;
+        ctx.emit(Event::new("bank", "revoke")
+            .with("owner", &transaction.sender)
+            .with("spender", &payload.spender)
+            .with("denom", &payload.denom));
+        Ok(())
+    }
+
+    async fn transfer_from(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let payload: TransferFromPayload = decode_payload(&transaction.data.payload)?;
+        let key = allowance_key(&payload.owner, &transaction.sender, &transaction.amount.denom);
+        let mut allowance = ctx.state::<Allowance>(&key).await?
+            .ok_or_else(|| invalid("body.amount", "insufficient allowance"))?;
+        if allowance.is_expired(ctx.height, ctx.timestamp) {
+            return Err(invalid("body.amount", "allowance is expired"));
+        }
+        allowance.spend(&transaction.amount)
+            .map_err(|_| invalid("body.amount", "insufficient allowance"))?;
+        if allowance.amount.is_zero() {
+            ctx.remove_state(&key)?;
+        } else {
+            ctx.set_state(&key, &allowance)?;
+        }
+
+        ctx.withdraw(&payload.owner, &transaction.amount).await?;
+        ctx.deposit(&payload.receiver, &transaction.amount).await?;
+        ctx.emit(transfer_event(&payload.owner, &payload.receiver, &transaction.amount)
+            .with("spender", &transaction.sender));
+        Ok(())
+    }
+
+    /// The spender is not the sender and the expiry is not already past
+    fn check_spender(&self, transaction: &Transaction, payload: &ApprovePayload, ctx: &ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        if payload.spender == transaction.sender {
+            return Err(invalid("body.data.payload.spender", "spender is the sender"));
+        }
+        if (ValidityWindow { valid_after: None, valid_until: payload.expiry }).is_expired(ctx.height, ctx.timestamp) {
+            return Err(invalid("body.data.payload.expiry", "allowance is expired"));
+        }
+        Ok(())
+    }
+
+    fn validate_approve(&self, payload: &ApprovePayload) -> Result<(), ApplicationError> {
+        self.check_network(&payload.spender, "body.data.payload.spender")?;
+        if payload.amount.is_zero() {
+            return Err(invalid("body.data.payload.amount", "amount must be greater than 0"));
+        }
+        Ok(())
+    }
+
+    /// Totals of the outputs per denom
+    fn multi_send_total(&self, payload: &MultiSendPayload) -> Result<Balances, ApplicationError> {
+        Ok(payload.outputs.iter()
+            .map(|output| output.amount.clone())
+            .collect::<Result<_, DomainError>>()?)
+    }
+
+    fn validate_multi_send(&self, payload: &MultiSendPayload) -> Result<(), ApplicationError> {
+        if payload.outputs.is_empty() {
+            return Err(ApplicationError::InvalidData(
+                [("body.data.payload.outputs".to_string(), "outputs are empty".to_string())].into()
+            ));
+        }
+        for (index, output) in payload.outputs.iter().enumerate() {
+            self.check_network(&output.receiver, &format!("body.data.payload.outputs.{}.receiver", index))?;
+            if output.amount.is_zero() {
+                return Err(ApplicationError::InvalidData(
+                    [(format!("body.data.payload.outputs.{}.amount", index), "amount must be greater than 0".to_string())].into()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_network(&self, address: &Address, key: &str) -> Result<(), ApplicationError> {
+        if address.network != self.config.hrp {
+            return Err(ApplicationError::InvalidData(
+                [(key.to_string(), "network is not valid".to_string())].into()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl App for BankApp<'_> {
+    fn name(&self) -> &str {
+        "bank"
+    }
+
+    fn operations(&self) -> &[&str] {
+        &["transfer", "multi_send", "approve", "increase_allowance", "revoke", "transfer_from"]
+    }
+
+    fn validate(&self, data: &AppData) -> Result<(), ApplicationError> {
+        match data.operation.as_str() {
+            "transfer" => {
+                let payload: TransferPayload = decode_payload(&data.payload)?;
+                self.check_network(&payload.receiver, "body.data.payload.receiver")
+            }
+            "multi_send" => self.validate_multi_send(&decode_payload(&data.payload)?),
+            "approve" | "increase_allowance" => self.validate_approve(&decode_payload(&data.payload)?),
+            "revoke" => {
+                let payload: RevokePayload = decode_payload(&data.payload)?;
+                self.check_network(&payload.spender, "body.data.payload.spender")
+            }
+            "transfer_from" => {
+                let payload: TransferFromPayload = decode_payload(&data.payload)?;
+                self.check_network(&payload.owner, "body.data.payload.owner")?;
+                self.check_network(&payload.receiver, "body.data.payload.receiver")
+            }
+            operation => Err(ApplicationError::NotFound(format!("bank/{}", operation))),
+        }
+    }
+
+    fn sender_debit(&self, data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        match data.operation.as_str() {
+            "multi_send" => Ok(self.multi_send_total(&decode_payload(&data.payload)?)?
+                .iter()
+                .map(|total| ("body.data.payload.outputs", total))
+                .collect()),
+            // `transfer_from` pays from the owner
+            "approve" | "increase_allowance" | "revoke" | "transfer_from" => Ok(vec![]),
+            _ => Ok(vec![("body.amount", amount.clone())]),
+        }
+    }
+
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        match transaction.data.operation.as_str() {
+            "transfer" => self.transfer(transaction, ctx).await,
+            "multi_send" => self.multi_send(transaction, ctx).await,
+            "approve" => self.approve(transaction, ctx).await,
+            "increase_allowance" => self.increase_allowance(transaction, ctx).await,
+            "revoke" => self.revoke(transaction, ctx).await,
+            "transfer_from" => self.transfer_from(transaction, ctx).await,
+            operation => Err(ApplicationError::NotFound(format!("bank/{}", operation))),
+        }
+    }
+
+    async fn query(&self, query: &str, payload: &Value, stores: Stores<'_>) -> Result<Value, ApplicationError> {
+        match query {
+            "balances" => {
+                let payload: BalancesQuery = decode_payload(payload)?;
+                let balances = stores.accounts.get(&payload.address).await
+                    .map(|acc| acc.balances)
+                    .unwrap_or_default();
+                Ok(serde_json::to_value(BalancesResponse(balances)).map_err(anyhow::Error::from)?)
+            }
+            "allowance" => {
+                let payload: AllowanceQuery = decode_payload(payload)?;
+                let bytes = stores.state.get(&allowance_key(&payload.owner, &payload.spender, &payload.denom)).await
+                    .ok_or_else(|| ApplicationError::NotFound(format!("allowance of {} over {}", payload.spender, payload.denom)))?;
+                let allowance: Allowance = serde_json::from_slice(&bytes).map_err(anyhow::Error::from)?;
+                Ok(serde_json::to_value(allowance).map_err(anyhow::Error::from)?)
+            }
+            _ => Err(ApplicationError::NotFound(format!("query {}", query))),
+        }
+    }
+}
+
+fn invalid(key: &str, reason: &str) -> ApplicationError {
+    ApplicationError::InvalidData([(key.to_string(), reason.to_string())].into())
+}
+
+/// App state key of the allowance of `spender` over `denom` of `owner`
+fn allowance_key(owner: &Address, spender: &Address, denom: &str) -> String {
+    format!("bank/allowances/{}/{}/{}", owner, spender, denom)
+}
+
+fn allowance_event(kind: &str, allowance: &Allowance) -> Event {
+    Event::new("bank", kind)
+        .with("owner", &allowance.owner)
+        .with("spender", &allowance.spender)
+        .with("amount", allowance.amount.value)
+        .with("denom", &allowance.amount.denom)
+}
+
+fn transfer_event(sender: &Address, receiver: &Address, amount: &Token) -> Event {
+    Event::new("bank", "transfer")
+        .with("sender", sender)
+        .with("receiver", receiver)
+        .with("amount", amount.value)
+        .with("denom", &amount.denom)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::acc_storage::tests::MockAccStorage;
+    use crate::application::common::acc_storage::AccStorage;
+    use crate::application::common::context::tests::MockStores;
+    use crate::application::common::app_router::{AppRegistry, AppRouter};
+    use crate::application::common::gas::{GasMeter, GasSchedule};
+    use crate::domain::models::fee::Fee;
+    use crate::domain::models::hash::Hash;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+
+    fn address(seed: u8) -> Address {
+        Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) }
+    }
+
+    fn transfer(receiver: &Address, amount: Token) -> Transaction {
+        Transaction::new(
+            Hash([0; 32]),
+            address(1),
+            AppData {
+                app: "bank".to_string(),
+                operation: "transfer".to_string(),
+                payload: serde_json::to_value(TransferPayload { receiver: receiver.clone() }).unwrap()
+            },
+            amount,
+            0,
+            Fee::new(100_000, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0; 64])
+        )
+    }
+
+    fn multi_send(outputs: Vec<(u8, Token)>) -> Transaction {
+        let outputs = outputs.into_iter()
+            .map(|(seed, amount)| Output { receiver: address(seed), amount })
+            .collect();
+        let mut transaction = transfer(&address(1), Token::new(1, "LOKI"));
+        transaction.data.operation = "multi_send".to_string();
+        transaction.data.payload = serde_json::to_value(MultiSendPayload { outputs }).unwrap();
+        transaction
+    }
+
+    fn context(mocks: &MockStores) -> ExecutionContext<'_> {
+        ExecutionContext::new(mocks.stores(), GasMeter::new(100_000, GasSchedule::default()))
+    }
+
+    async fn balance(acc_storage: &MockAccStorage, seed: u8) -> u128 {
+        acc_storage.get(&address(seed)).await.map_or(0, |acc| acc.balances.get("LOKI").value)
+    }
+
+    #[tokio::test]
+    async fn test_transfer() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        let mut registry = AppRegistry::new(mocks.stores());
+        registry.register(Box::new(BankApp { config: &config }));
+
+        // the receiver account does not exist yet
+        let mut ctx = context(&mocks);
+        registry.execute(&transfer(&address(2), Token::new(30, "LOKI")), &mut ctx).await.unwrap();
+        let events = ctx.commit().await;
+        assert_eq!(balance(acc_storage, 1).await, 70);
+        assert_eq!(balance(acc_storage, 2).await, 30);
+        assert_eq!(acc_storage.get(&address(2)).await.unwrap().nonce, 0);
+        assert_eq!(events[0].attributes["receiver"], address(2).to_string());
+
+        let balances = registry.query("bank", "balances", &serde_json::json!({ "address": address(2) })).await.unwrap();
+        assert_eq!(balances, serde_json::json!({ "LOKI": "30" }));
+        let balances = registry.query("bank", "balances", &serde_json::json!({ "address": address(3) })).await.unwrap();
+        assert_eq!(balances, serde_json::json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_to_self() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        let mut ctx = context(&mocks);
+        app.execute(&transfer(&address(1), Token::new(100, "LOKI")), &mut ctx).await.unwrap();
+        ctx.commit().await;
+        assert_eq!(balance(acc_storage, 1).await, 100);
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_funds() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        let mut ctx = context(&mocks);
+        assert!(app.execute(&transfer(&address(2), Token::new(101, "LOKI")), &mut ctx).await.is_err());
+        assert!(app.execute(&transfer(&address(2), Token::new(1, "USDT")), &mut ctx).await.is_err());
+        drop(ctx);
+        assert_eq!(balance(acc_storage, 1).await, 100);
+        assert!(acc_storage.get(&address(2)).await.is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = NodeConfig::default();
+        let app = BankApp { config: &config };
+
+        assert!(app.validate(&transfer(&address(2), Token::new(1, "LOKI")).data).is_ok());
+
+        let foreign = Address { network: "otherchain".to_string(), vk: VerifyKey::Ed25519([2; 32]) };
+        let result = app.validate(&transfer(&foreign, Token::new(1, "LOKI")).data);
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.receiver")));
+
+        let mut data = transfer(&address(2), Token::new(1, "LOKI")).data;
+        data.payload = serde_json::json!({ "to": address(2) });
+        assert!(app.validate(&data).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_multi_send() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        acc_storage.deposit(&address(1), &Token::new(50, "USDT")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        let mut ctx = context(&mocks);
+        let transaction = multi_send(vec![
+            (2, Token::new(10, "LOKI")),
+            (3, Token::new(20, "USDT")),
+            (2, Token::new(5, "LOKI")),
+        ]);
+        app.execute(&transaction, &mut ctx).await.unwrap();
+        assert_eq!(ctx.commit().await.len(), 3);
+
+        assert_eq!(balance(acc_storage, 1).await, 85);
+        assert_eq!(acc_storage.get(&address(1)).await.unwrap().balances.get("USDT").value, 30);
+        assert_eq!(balance(acc_storage, 2).await, 15);
+        assert_eq!(acc_storage.get(&address(3)).await.unwrap().balances.get("USDT").value, 20);
+        // the transaction amount is not moved
+        assert_eq!(transaction.amount, Token::new(1, "LOKI"));
+
+        // every output adds a read and a write
+        let schedule = GasSchedule::default();
+        let mut gas_used = vec![];
+        for count in 1..=3 {
+            let mut ctx = context(&mocks);
+            app.execute(&multi_send(vec![(2, Token::new(1, "LOKI")); count]), &mut ctx).await.unwrap();
+            gas_used.push(ctx.meter.used());
+        }
+        assert_eq!(gas_used[1] - gas_used[0], schedule.read + schedule.write);
+        assert_eq!(gas_used[2] - gas_used[1], schedule.read + schedule.write);
+    }
+
+    #[tokio::test]
+    async fn test_multi_send_insufficient_funds() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        acc_storage.deposit(&address(1), &Token::new(10, "USDT")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        // every output alone is covered, the USDT total is not
+        let mut ctx = context(&mocks);
+        let transaction = multi_send(vec![
+            (2, Token::new(10, "LOKI")),
+            (3, Token::new(6, "USDT")),
+            (4, Token::new(6, "USDT")),
+        ]);
+        let result = app.execute(&transaction, &mut ctx).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors["body.data.payload.outputs"] == "insufficient funds"));
+        drop(ctx);
+        assert_eq!(balance(acc_storage, 1).await, 100);
+        assert!(acc_storage.get(&address(2)).await.is_none());
+    }
+
+    #[test]
+    fn test_validate_multi_send() {
+        let config = NodeConfig::default();
+        let app = BankApp { config: &config };
+
+        assert!(app.validate(&multi_send(vec![(2, Token::new(1, "LOKI")), (3, Token::new(1, "USDT"))]).data).is_ok());
+        assert!(app.validate(&multi_send(vec![]).data).is_err());
+
+        let result = app.validate(&multi_send(vec![(2, Token::new(1, "LOKI")), (3, Token::new(0, "LOKI"))]).data);
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.outputs.1.amount")));
+
+        let mut data = multi_send(vec![(2, Token::new(1, "LOKI"))]).data;
+        data.payload["outputs"][0]["receiver"] = serde_json::to_value(
+            Address { network: "otherchain".to_string(), vk: VerifyKey::Ed25519([2; 32]) }
+        ).unwrap();
+        let result = app.validate(&data);
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.outputs.0.receiver")));
+    }
+
+    fn allowance_op(sender: u8, operation: &str, amount: Token, payload: Value) -> Transaction {
+        let mut transaction = transfer(&address(1), amount);
+        transaction.sender = address(sender);
+        transaction.data.operation = operation.to_string();
+        transaction.data.payload = payload;
+        transaction
+    }
+
+    fn approve(operation: &str, spender: u8, value: u128, expiry: Option<ValidityBound>) -> Transaction {
+        let payload = ApprovePayload { spender: address(spender), amount: Token::new(value, "LOKI"), expiry };
+        allowance_op(1, operation, Token::new(1, "LOKI"), serde_json::to_value(payload).unwrap())
+    }
+
+    fn transfer_from(spender: u8, value: u128) -> Transaction {
+        let payload = TransferFromPayload { owner: address(1), receiver: address(3) };
+        allowance_op(spender, "transfer_from", Token::new(value, "LOKI"), serde_json::to_value(payload).unwrap())
+    }
+
+    /// Executes `transaction` in the block at `height` and commits it if it succeeded
+    async fn run_at(app: &BankApp<'_>, mocks: &MockStores, height: u64, transaction: Transaction) -> Result<(), ApplicationError> {
+        let mut ctx = context(mocks).at_block(height, 0);
+        app.execute(&transaction, &mut ctx).await?;
+        ctx.commit().await;
+        Ok(())
+    }
+
+    async fn allowance(app: &BankApp<'_>, mocks: &MockStores, spender: u8) -> Option<Allowance> {
+        let query = serde_json::json!({ "owner": address(1), "spender": address(spender), "denom": "LOKI" });
+        app.query("allowance", &query, mocks.stores()).await.ok()
+            .map(|value| serde_json::from_value(value).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_approve_and_transfer_from() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        run_at(&app, &mocks, 0, approve("approve", 2, 50, None)).await.unwrap();
+        run_at(&app, &mocks, 0, transfer_from(2, 30)).await.unwrap();
+        assert_eq!((balance(acc_storage, 1).await, balance(acc_storage, 3).await), (70, 30));
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(20, "LOKI"));
+
+        let result = run_at(&app, &mocks, 0, transfer_from(2, 21)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors["body.amount"] == "insufficient allowance"));
+        let result = run_at(&app, &mocks, 0, transfer_from(4, 1)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors["body.amount"] == "insufficient allowance"));
+
+        // a spent allowance is removed
+        run_at(&app, &mocks, 0, transfer_from(2, 20)).await.unwrap();
+        assert_eq!(balance(acc_storage, 3).await, 50);
+        assert!(allowance(&app, &mocks, 2).await.is_none());
+
+        // approving replaces the allowance, even above the balance of the owner
+        run_at(&app, &mocks, 0, approve("approve", 2, 1000, None)).await.unwrap();
+        run_at(&app, &mocks, 0, approve("approve", 2, 60, None)).await.unwrap();
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(60, "LOKI"));
+        assert!(run_at(&app, &mocks, 0, transfer_from(2, 60)).await.is_err());
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(60, "LOKI"));
+
+        let result = run_at(&app, &mocks, 0, approve("approve", 1, 10, None)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.spender")));
+    }
+
+    #[tokio::test]
+    async fn test_increase_and_revoke() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let app = BankApp { config: &config };
+
+        run_at(&app, &mocks, 0, approve("increase_allowance", 2, 10, Some(ValidityBound::Timestamp(100)))).await.unwrap();
+        let mut ctx = context(&mocks);
+        app.execute(&approve("increase_allowance", 2, 15, None), &mut ctx).await.unwrap();
+        let events = ctx.commit().await;
+        assert_eq!((events[0].kind.as_str(), events[0].attributes["amount"].as_str()), ("increase_allowance", "25"));
+        let allowance_2 = allowance(&app, &mocks, 2).await.unwrap();
+        assert_eq!((allowance_2.amount.value, allowance_2.expiry), (25, Some(ValidityBound::Timestamp(100))));
+
+        let revoke = |spender: u8| allowance_op(1, "revoke", Token::new(1, "LOKI"), serde_json::json!({ "spender": address(spender), "denom": "LOKI" }));
+        run_at(&app, &mocks, 0, revoke(2)).await.unwrap();
+        assert!(allowance(&app, &mocks, 2).await.is_none());
+        assert!(matches!(run_at(&app, &mocks, 0, revoke(2)).await, Err(ApplicationError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_allowance_expiry() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        run_at(&app, &mocks, 5, approve("approve", 2, 50, Some(ValidityBound::Height(10)))).await.unwrap();
+        run_at(&app, &mocks, 10, transfer_from(2, 10)).await.unwrap();
+        let result = run_at(&app, &mocks, 11, transfer_from(2, 10)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors["body.amount"] == "allowance is expired"));
+        assert_eq!(balance(acc_storage, 3).await, 10);
+
+        // the expired allowance is left for the query and is not increased
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(40, "LOKI"));
+        run_at(&app, &mocks, 11, approve("increase_allowance", 2, 5, None)).await.unwrap();
+        let allowance_2 = allowance(&app, &mocks, 2).await.unwrap();
+        assert_eq!((allowance_2.amount.value, allowance_2.expiry), (5, None));
+
+        let result = run_at(&app, &mocks, 11, approve("approve", 2, 5, Some(ValidityBound::Height(10)))).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.expiry")));
+    }
+
+    #[tokio::test]
+    async fn test_amounts_above_u64() {
+        let config = NodeConfig::default();
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        let big = u64::MAX as u128 * 4;
+        acc_storage.deposit(&address(1), &Token::new(big * 3, "LOKI")).await.unwrap();
+        let app = BankApp { config: &config };
+
+        let transaction = multi_send(vec![(2, Token::new(big, "LOKI")), (3, Token::new(big, "LOKI"))]);
+        assert_eq!(transaction.data.payload["outputs"][0]["amount"]["value"], big.to_string());
+        app.validate(&transaction.data).unwrap();
+        run_at(&app, &mocks, 0, transaction).await.unwrap();
+        assert_eq!((balance(acc_storage, 1).await, balance(acc_storage, 2).await), (big, big));
+
+        run_at(&app, &mocks, 0, approve("approve", 2, big, None)).await.unwrap();
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(big, "LOKI"));
+        run_at(&app, &mocks, 0, transfer_from(2, big - 1)).await.unwrap();
+        assert_eq!(allowance(&app, &mocks, 2).await.unwrap().amount, Token::new(1, "LOKI"));
+
+        let mut registry = AppRegistry::new(mocks.stores());
+        registry.register(Box::new(BankApp { config: &config }));
+        let balances = registry.query("bank", "balances", &serde_json::json!({ "address": address(3) })).await.unwrap();
+        assert_eq!(balances, serde_json::json!({ "LOKI": (big * 2 - 1).to_string() }));
+    }
+
+    #[test]
+    fn test_validate_allowances() {
+        let config = NodeConfig::default();
+        let app = BankApp { config: &config };
+        let foreign = Address { network: "otherchain".to_string(), vk: VerifyKey::Ed25519([2; 32]) };
+
+        assert!(app.validate(&approve("approve", 2, 1, None).data).is_ok());
+        let result = app.validate(&approve("increase_allowance", 2, 0, None).data);
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.amount")));
+
+        let mut data = approve("approve", 2, 1, None).data;
+        data.payload["spender"] = serde_json::to_value(&foreign).unwrap();
+        assert!(app.validate(&data).is_err());
+
+        let mut data = transfer_from(2, 1).data;
+        assert!(app.validate(&data).is_ok());
+        data.payload["receiver"] = serde_json::to_value(&foreign).unwrap();
+        let result = app.validate(&data);
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.receiver")));
+    }
+}
@@ -0,0 +1,346 @@
+//! Merkle tree over the transactions and the receipts of a block
+//!
+//! The tree follows RFC 6962: leaves are hashed as `H(0x00 || leaf)` and nodes as
+//! `H(0x01 || left || right)`, so a node can never be passed off as a leaf (second preimage).
+//! An odd node at the end of a level is promoted to the next level as is, never duplicated.
+//! The root of an empty tree is `H()`.
+//!
+//! The `[Hasher]` is expected to be tagged with `[HashDomain::MerkleNode]`
+//!
+//! [HashDomain::MerkleNode]: crate::application::common::hasher::HashDomain::MerkleNode
+
+use crate::application::common::hasher::Hasher;
+use crate::domain::models::block::{Block, BlockHeader};
+use crate::domain::models::hash::Hash;
+use crate::domain::models::merkle_proof::MerkleProof;
+use crate::domain::models::receipt::Receipt;
+use crate::domain::models::transaction::Transaction;
+use crate::domain::services::encoding::encode;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+async fn leaf_hash(hasher: &dyn Hasher, leaf: &[u8]) -> Hash {
+    hasher.hash(&[&[LEAF_PREFIX], leaf].concat()).await
+}
+
+async fn node_hash(hasher: &dyn Hasher, left: &Hash, right: &Hash) -> Hash {
+    hasher.hash(&[&[NODE_PREFIX], left.0.as_slice(), right.0.as_slice()].concat()).await
+}
+
+async fn leaf_level<T: AsRef<[u8]>>(hasher: &dyn Hasher, leaves: &[T]) -> Vec<Hash> {
+    let mut level = Vec::with_capacity(leaves.len());
+    for leaf in leaves {
+        level.push(leaf_hash(hasher, leaf.as_ref()).await);
+    }
+    level
+}
+
+async fn parent_level(hasher: &dyn Hasher, level: &[Hash]) -> Vec<Hash> {
+    let mut parents = Vec::with_capacity(level.len().div_ceil(2));
+    for pair in level.chunks(2) {
+        match pair {
+            [left, right] => parents.push(node_hash(hasher, left, right).await),
+            [single] => parents.push(single.clone()),
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        }
+    }
+    parents
+}
+
+/// Computes the root of the tree over `leaves`
+pub async fn merkle_root<T: AsRef<[u8]>>(hasher: &dyn Hasher, leaves: &[T]) -> Hash {
+    if leaves.is_empty() {
+        return hasher.hash(&[]).await;
+    }
+
+    let mut level = leaf_level(hasher, leaves).await;
+    while level.len() > 1 {
+        level = parent_level(hasher, &level).await;
+    }
+    level.swap_remove(0)
+}
+
+/// Builds the inclusion proof of the leaf at `index`, `None` if it is out of range
+pub async fn merkle_proof<T: AsRef<[u8]>>(hasher: &dyn Hasher, leaves: &[T], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut level = leaf_level(hasher, leaves).await;
+    let mut position = index;
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        // the last node of an odd level has no sibling and is promoted
+        if let Some(sibling) = level.get(position ^ 1) {
+            siblings.push(sibling.clone());
+        }
+        level = parent_level(hasher, &level).await;
+        position /= 2;
+    }
+
+    Some(MerkleProof {
+        index: index as u64,
+        leaf_count: leaves.len() as u64,
+        siblings
+    })
+}
+
+/// Checks that `leaf` is at `proof.index` of the tree with the given `root`
+///
+/// The proof must have exactly as many siblings as the tree of `proof.leaf_count` requires
+pub async fn verify_proof(hasher: &dyn Hasher, root: &Hash, leaf: &[u8], proof: &MerkleProof) -> bool {
+    if proof.index >= proof.leaf_count {
+        return false;
+    }
+
+    let mut index = proof.index;
+    let mut last = proof.leaf_count - 1;
+    let mut hash = leaf_hash(hasher, leaf).await;
+    for sibling in &proof.siblings {
+        if last == 0 {
+            return false;
+        }
+        if index & 1 == 1 || index == last {
+            hash = node_hash(hasher, sibling, &hash).await;
+            // skip the levels where this node is promoted without a sibling
+            while index & 1 == 0 && index != 0 {
+                index >>= 1;
+                last >>= 1;
+            }
+        } else {
+            hash = node_hash(hasher, &hash, sibling).await;
+        }
+        index >>= 1;
+        last >>= 1;
+    }
+
+    last == 0 && hash == *root
+}
+
+/// Computes `[BlockHeader::merkle_root]` over the transaction hashes in block order
+pub async fn transactions_root(hasher: &dyn Hasher, transactions: &[Transaction]) -> Hash {
+    let hashes = transactions.iter().map(|tx| tx.hash.clone()).collect::<Vec<_>>();
+    merkle_root(hasher, &hashes).await
+}
+
+/// Builds the inclusion proof of the transaction with `tx_hash`, `None` if it is not in the block
+pub async fn prove_transaction(hasher: &dyn Hasher, block: &Block, tx_hash: &Hash) -> Option<MerkleProof> {
+    let index = block.transactions.iter().position(|tx| tx.hash == *tx_hash)?;
+    let hashes = block.transactions.iter().map(|tx| tx.hash.clone()).collect::<Vec<_>>();
+    merkle_proof(hasher, &hashes, index).await
+}
+
+/// Checks that the transaction with `tx_hash` is included in the block of `header`
+pub async fn verify_transaction(hasher: &dyn Hasher, header: &BlockHeader, tx_hash: &Hash, proof: &MerkleProof) -> bool {
+    verify_proof(hasher, &header.merkle_root, &tx_hash.0, proof).await
+}
+
+/// Computes `[BlockHeader::receipts_root]` over the encoded receipts in block order
+pub async fn receipts_root(hasher: &dyn Hasher, receipts: &[Receipt]) -> Hash {
+    let leaves = receipts.iter().map(encode).collect::<Vec<_>>();
+    merkle_root(hasher, &leaves).await
+}
+
+/// Builds the inclusion proof of the receipt of `tx_hash`, `None` if it is not among `receipts`
+pub async fn prove_receipt(hasher: &dyn Hasher, receipts: &[Receipt], tx_hash: &Hash) -> Option<MerkleProof> {
+    let index = receipts.iter().position(|receipt| receipt.tx_hash == *tx_hash)?;
+    let leaves = receipts.iter().map(encode).collect::<Vec<_>>();
+    merkle_proof(hasher, &leaves, index).await
+}
+
+/// Checks that `receipt` is committed by the block of `header`
+pub async fn verify_receipt(hasher: &dyn Hasher, header: &BlockHeader, receipt: &Receipt, proof: &MerkleProof) -> bool {
+    receipt.block_height == header.height
+        && proof.index == receipt.index as u64
+        && verify_proof(hasher, &header.receipts_root, &encode(receipt), proof).await
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::address::Address;
+    use crate::domain::models::app_data::AppData;
+    use crate::domain::models::fee::Fee;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+    use crate::domain::models::token::Token;
+    use crate::domain::models::transaction::TxState;
+    use crate::application::common::hasher::tests::PlainSha256Hasher as PlainSha256;
+    use serde_json::Value;
+
+    /// Leaves and roots of the first 0..=8 leaves from the Certificate Transparency test suite
+    const LEAVES: [&str; 8] = [
+        "", "00", "10", "2021", "3031", "40414243", "5051525354555657", "606162636465666768696a6b6c6d6e6f",
+    ];
+    const ROOTS: [&str; 9] = [
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+        "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+        "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+        "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+        "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+        "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+        "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+    ];
+
+    fn leaves() -> Vec<Vec<u8>> {
+        LEAVES.iter().map(|leaf| hex::decode(leaf).unwrap()).collect()
+    }
+
+    fn make_transaction(seed: u8) -> Transaction {
+        Transaction::new(
+            Hash([seed; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+            Token { value: 10, denom: "LOKI".to_string() },
+            0,
+            Fee::new(10, Token::new(1, "LOKI")),
+            seed as u64,
+            Signature::Ed25519([0; 64])
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rfc6962_roots() {
+        let leaves = leaves();
+        for (count, root) in ROOTS.iter().enumerate() {
+            assert_eq!(hex::encode(merkle_root(&PlainSha256, &leaves[..count]).await.0), *root);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proofs() {
+        let leaves = leaves();
+        for count in 1..=leaves.len() {
+            let leaves = &leaves[..count];
+            let root = merkle_root(&PlainSha256, leaves).await;
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&PlainSha256, leaves, index).await.unwrap();
+                assert!(verify_proof(&PlainSha256, &root, leaf, &proof).await);
+                assert!(!verify_proof(&PlainSha256, &root, b"other", &proof).await);
+
+                let mut wrong_index = proof.clone();
+                wrong_index.index = (proof.index + 1) % proof.leaf_count;
+                if count > 1 && leaves[wrong_index.index as usize] != *leaf {
+                    assert!(!verify_proof(&PlainSha256, &root, leaf, &wrong_index).await);
+                }
+
+                let mut extra_sibling = proof.clone();
+                extra_sibling.siblings.push(root.clone());
+                assert!(!verify_proof(&PlainSha256, &root, leaf, &extra_sibling).await);
+            }
+            assert!(merkle_proof(&PlainSha256, leaves, count).await.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_proof_bounds() {
+        let leaves = leaves();
+        let root = merkle_root(&PlainSha256, &leaves).await;
+        let mut proof = merkle_proof(&PlainSha256, &leaves, 2).await.unwrap();
+
+        proof.leaf_count = 2;
+        assert!(!verify_proof(&PlainSha256, &root, &leaves[2], &proof).await);
+
+        proof.leaf_count = 0;
+        proof.index = 0;
+        assert!(!verify_proof(&PlainSha256, &root, &leaves[2], &proof).await);
+    }
+
+    #[tokio::test]
+    async fn test_second_preimage() {
+        let leaves = &leaves()[..4];
+        let root = merkle_root(&PlainSha256, leaves).await;
+        let level = parent_level(&PlainSha256, &leaf_level(&PlainSha256, leaves).await).await;
+
+        // inner nodes presented as leaves do not produce the same root
+        assert_ne!(merkle_root(&PlainSha256, &level).await, root);
+
+        // an inner node is not accepted as a leaf with a shorter path
+        let proof = MerkleProof { index: 0, leaf_count: 2, siblings: vec![level[1].clone()] };
+        assert!(!verify_proof(&PlainSha256, &root, &level[0].0, &proof).await);
+
+        // nor is the concatenation of its children
+        let children = leaf_level(&PlainSha256, &leaves[..2]).await
+            .iter()
+            .flat_map(|hash| hash.0)
+            .collect::<Vec<_>>();
+        assert!(!verify_proof(&PlainSha256, &root, &children, &proof).await);
+    }
+
+    #[tokio::test]
+    async fn test_block_inclusion() {
+        let transactions = (0..5).map(make_transaction).collect::<Vec<_>>();
+        let root = transactions_root(&PlainSha256, &transactions).await;
+        let block = Block::new(
+            Hash([0; 32]),
+            1,
+            0,
+            Hash([0; 32]),
+            root,
+            Hash([0; 32]),
+            Hash([0; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            Token { value: 10, denom: "LOKI".to_string() },
+            Signature::Ed25519([0; 64]),
+            transactions.clone()
+        );
+
+        for tx in &transactions {
+            let proof = prove_transaction(&PlainSha256, &block, &tx.hash).await.unwrap();
+            assert!(verify_transaction(&PlainSha256, &block.header, &tx.hash, &proof).await);
+            assert!(!verify_transaction(&PlainSha256, &block.header, &Hash([9; 32]), &proof).await);
+        }
+        assert!(prove_transaction(&PlainSha256, &block, &Hash([9; 32])).await.is_none());
+
+        let empty = transactions_root(&PlainSha256, &[]).await;
+        assert_eq!(hex::encode(empty.0), ROOTS[0]);
+    }
+
+    #[tokio::test]
+    async fn test_receipt_inclusion() {
+        let receipts = (0..3u8).map(|seed| Receipt {
+            tx_hash: Hash([seed; 32]),
+            block_height: 1,
+            block_hash: Hash([0; 32]),
+            index: seed as u32,
+            state: TxState::Confirmed,
+            gas_used: 10,
+            fee: Token::new(10, "LOKI"),
+            error: None,
+            events: vec![]
+        }).collect::<Vec<_>>();
+        let mut block = Block::new(
+            Hash([0; 32]),
+            1,
+            0,
+            Hash([0; 32]),
+            Hash([0; 32]),
+            receipts_root(&PlainSha256, &receipts).await,
+            Hash([0; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            Token { value: 10, denom: "LOKI".to_string() },
+            Signature::Ed25519([0; 64]),
+            vec![]
+        );
+
+        for receipt in &receipts {
+            let proof = prove_receipt(&PlainSha256, &receipts, &receipt.tx_hash).await.unwrap();
+            assert!(verify_receipt(&PlainSha256, &block.header, receipt, &proof).await);
+
+            // the block hash is not committed, every other field is
+            let sealed = Receipt { block_hash: Hash([7; 32]), ..receipt.clone() };
+            assert!(verify_receipt(&PlainSha256, &block.header, &sealed, &proof).await);
+            let forged = Receipt { gas_used: 1, ..receipt.clone() };
+            assert!(!verify_receipt(&PlainSha256, &block.header, &forged, &proof).await);
+        }
+        assert!(prove_receipt(&PlainSha256, &receipts, &Hash([9; 32])).await.is_none());
+
+        let proof = prove_receipt(&PlainSha256, &receipts, &receipts[0].tx_hash).await.unwrap();
+        block.header.height = 2;
+        assert!(!verify_receipt(&PlainSha256, &block.header, &receipts[0], &proof).await);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod merkle;
+pub mod validation;
+pub mod receipts;
@@ -0,0 +1,114 @@
+use crate::application::block::merkle::receipts_root;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::hasher::Hasher;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::receipt_storage::ReceiptStorage;
+use crate::domain::models::block::BlockHeader;
+use crate::domain::models::receipt::Receipt;
+use async_trait::async_trait;
+
+pub struct SaveReceiptsRequest {
+    /// header of the sealed block, its hash is known
+    pub header: BlockHeader,
+    /// receipts of the block in transaction order
+    pub receipts: Vec<Receipt>
+}
+
+/// Stores the receipts of a sealed block
+///
+/// Every receipt is stamped with the hash of the block, which the receipts root does not commit to.
+/// Nothing is stored unless the receipts belong to the block and match its receipts root
+///
+/// The `[Hasher]` is expected to be tagged with `[HashDomain::MerkleNode]`
+///
+/// [HashDomain::MerkleNode]: crate::application::common::hasher::HashDomain::MerkleNode
+pub struct SaveReceipts<'a> {
+    pub hasher: &'a dyn Hasher,
+    pub receipt_storage: &'a dyn ReceiptStorage
+}
+
+#[async_trait]
+impl Interactor<SaveReceiptsRequest> for SaveReceipts<'_> {
+    async fn execute(&self, data: SaveReceiptsRequest) -> Result<(), ApplicationError> {
+        if data.receipts.iter().any(|receipt| receipt.block_height != data.header.height) {
+            return Err(ApplicationError::InvalidData(
+                [("receipts".to_string(), "receipt of another block".to_string())].into()
+            ));
+        }
+        if receipts_root(self.hasher, &data.receipts).await != data.header.receipts_root {
+            return Err(ApplicationError::InvalidData(
+                [("receipts".to_string(), "receipts do not match the receipts root".to_string())].into()
+            ));
+        }
+
+        for receipt in data.receipts {
+            self.receipt_storage.set(Receipt { block_hash: data.header.hash.clone(), ..receipt }).await;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::hasher::tests::PlainSha256Hasher;
+    use crate::application::common::receipt_storage::tests::MockReceiptStorage;
+    use crate::application::transaction::get_receipt::{GetReceipt, GetReceiptRequest};
+    use crate::domain::models::address::Address;
+    use crate::domain::models::block::Block;
+    use crate::domain::models::hash::Hash;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+    use crate::domain::models::token::Token;
+    use crate::domain::models::transaction::TxState;
+
+    fn make_receipt(seed: u8, block_height: u64) -> Receipt {
+        Receipt {
+            tx_hash: Hash([seed; 32]),
+            block_height,
+            block_hash: Hash([0; 32]),
+            index: seed as u32,
+            state: TxState::Confirmed,
+            gas_used: 10,
+            fee: Token::new(10, "LOKI"),
+            error: None,
+            events: vec![]
+        }
+    }
+
+    async fn make_header(receipts: &[Receipt]) -> BlockHeader {
+        Block::new(
+            Hash([7; 32]),
+            1,
+            0,
+            Hash([0; 32]),
+            Hash([0; 32]),
+            receipts_root(&PlainSha256Hasher, receipts).await,
+            Hash([0; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            Token::new(10, "LOKI"),
+            Signature::Ed25519([0; 64]),
+            vec![]
+        ).header
+    }
+
+    #[tokio::test]
+    async fn test_save_receipts() {
+        let receipt_storage = MockReceiptStorage::new();
+        let interactor = SaveReceipts { hasher: &PlainSha256Hasher, receipt_storage: &receipt_storage };
+        let receipts = vec![make_receipt(0, 1), make_receipt(1, 1)];
+        let header = make_header(&receipts).await;
+
+        // receipts that are not the ones of the header are not stored
+        let result = interactor.execute(SaveReceiptsRequest { header: header.clone(), receipts: receipts[..1].to_vec() }).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(_))));
+        let result = interactor.execute(SaveReceiptsRequest { header: header.clone(), receipts: vec![make_receipt(0, 2)] }).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(_))));
+        assert!(receipt_storage.get(&Hash([0; 32])).await.is_none());
+
+        interactor.execute(SaveReceiptsRequest { header, receipts: receipts.clone() }).await.unwrap();
+        let get_receipt = GetReceipt { receipt_storage: &receipt_storage };
+        let receipt = get_receipt.execute(GetReceiptRequest { hash: Hash([1; 32]) }).await.unwrap();
+        assert_eq!(receipt, Receipt { block_hash: Hash([7; 32]), ..receipts[1].clone() });
+    }
+}
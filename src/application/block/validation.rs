@@ -0,0 +1,87 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::block::Block;
+use std::collections::HashMap;
+
+/// Checks that every transaction of the block is within its validity window at the block height and timestamp
+pub fn validate_validity_windows(block: &Block) -> Result<(), ApplicationError> {
+    let errors = block.transactions
+        .iter()
+        .enumerate()
+        .filter(|(_, tx)| !tx.validity().contains(block.header.height, block.header.timestamp))
+        .map(|(index, _)| (format!("transactions.{}", index), "transaction is outside its validity window".to_string()))
+        .collect::<HashMap<_, _>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ApplicationError::InvalidData(errors))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::address::Address;
+    use crate::domain::models::app_data::AppData;
+    use crate::domain::models::fee::Fee;
+    use crate::domain::models::hash::Hash;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+    use crate::domain::models::token::Token;
+    use crate::domain::models::transaction::Transaction;
+    use crate::domain::models::validity::{ValidityBound, ValidityWindow};
+    use serde_json::Value;
+
+    fn make_transaction(seed: u8, validity: ValidityWindow) -> Transaction {
+        Transaction::new(
+            Hash([seed; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+            Token::new(10, "LOKI"),
+            0,
+            Fee::new(10, Token::new(1, "LOKI")),
+            seed as u64,
+            Signature::Ed25519([0; 64])
+        ).with_validity(validity)
+    }
+
+    fn make_block(height: u64, transactions: Vec<Transaction>) -> Block {
+        Block::new(
+            Hash([0; 32]),
+            height,
+            0,
+            Hash([0; 32]),
+            Hash([0; 32]),
+            Hash([0; 32]),
+            Hash([0; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) },
+            Token::new(10, "LOKI"),
+            Signature::Ed25519([0; 64]),
+            transactions
+        )
+    }
+
+    #[test]
+    fn test_validity_windows() {
+        let transactions = vec![
+            make_transaction(0, ValidityWindow::default()),
+            make_transaction(1, ValidityWindow { valid_after: Some(ValidityBound::Height(10)), valid_until: None }),
+            make_transaction(2, ValidityWindow { valid_after: None, valid_until: Some(ValidityBound::Height(10)) }),
+        ];
+
+        assert!(validate_validity_windows(&make_block(10, transactions.clone())).is_ok());
+
+        match validate_validity_windows(&make_block(11, transactions.clone())) {
+            Err(ApplicationError::InvalidData(errors)) => {
+                assert_eq!(errors.keys().collect::<Vec<_>>(), vec!["transactions.2"]);
+            }
+            _ => panic!("expired transaction must be rejected"),
+        }
+        match validate_validity_windows(&make_block(9, transactions)) {
+            Err(ApplicationError::InvalidData(errors)) => {
+                assert_eq!(errors.keys().collect::<Vec<_>>(), vec!["transactions.1"]);
+            }
+            _ => panic!("transaction before its window must be rejected"),
+        }
+    }
+}
@@ -1,11 +1,38 @@
-use crate::domain::models::account::Account;
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::account::{Account, Balances};
 use crate::domain::models::address::Address;
+use crate::domain::models::token::Token;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait AccStorage: Send + Sync {
     async fn get(&self, key: &Address) -> Option<Account>;
     async fn set(&self, key: Address, acc: Account);
+
+    /// Adds `amount` to the balance of its denom, a missing account is created
+    ///
+    /// The balances are left unchanged on overflow
+    async fn deposit(&self, key: &Address, amount: &Token) -> Result<Account, ApplicationError> {
+        let mut acc = self.get(key).await.unwrap_or_else(|| Account {
+            address: key.clone(),
+            nonce: 0,
+            balances: Balances::new()
+        });
+        acc.balances.add(amount)?;
+        self.set(key.clone(), acc.clone()).await;
+        Ok(acc)
+    }
+
+    /// Subtracts `amount` from the balance of its denom
+    ///
+    /// The balances are left unchanged on underflow
+    async fn withdraw(&self, key: &Address, amount: &Token) -> Result<Account, ApplicationError> {
+        let mut acc = self.get(key).await
+            .ok_or_else(|| ApplicationError::NotFound(format!("account {}", key)))?;
+        acc.balances.sub(amount)?;
+        self.set(key.clone(), acc.clone()).await;
+        Ok(acc)
+    }
 }
 
 #[cfg(test)]
@@ -15,6 +42,7 @@ pub mod tests {
     use std::collections::HashMap;
     use std::sync::Arc;
     use tokio::sync::RwLock;
+    use crate::domain::exceptions::DomainError;
     use crate::domain::models::signature::VerifyKey;
 
     pub struct MockAccStorage {
@@ -48,20 +76,57 @@ pub mod tests {
 
         let address = Address {
             network: "lokichain".to_string(),
-            vk: VerifyKey([0; 32])
+            vk: VerifyKey::Ed25519([0; 32])
         };
 
         let account = Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token {
+            balances: Token {
                 value: 0,
                 denom: "LOKI".to_string()
-            }
+            }.into()
         };
 
         storage.set(address.clone(), account.clone()).await;
         let result = storage.get(&address).await.unwrap();
         assert_eq!(account, result);
     }
+
+    #[tokio::test]
+    async fn test_balance_updates() {
+        let storage = MockAccStorage::new();
+        let address = Address {
+            network: "lokichain".to_string(),
+            vk: VerifyKey::Ed25519([0; 32])
+        };
+
+        assert!(matches!(
+            storage.withdraw(&address, &Token::new(1, "LOKI")).await,
+            Err(ApplicationError::NotFound(_))
+        ));
+
+        let acc = storage.deposit(&address, &Token::new(100, "LOKI")).await.unwrap();
+        assert_eq!(acc.balances.get("LOKI"), Token::new(100, "LOKI"));
+        let acc = storage.withdraw(&address, &Token::new(40, "LOKI")).await.unwrap();
+        assert_eq!(acc.balances.get("LOKI"), Token::new(60, "LOKI"));
+
+        assert!(matches!(
+            storage.withdraw(&address, &Token::new(61, "LOKI")).await,
+            Err(ApplicationError::Domain(DomainError::Underflow))
+        ));
+        assert!(matches!(
+            storage.deposit(&address, &Token::new(u128::MAX, "LOKI")).await,
+            Err(ApplicationError::Domain(DomainError::Overflow))
+        ));
+        assert!(matches!(
+            storage.withdraw(&address, &Token::new(1, "USDT")).await,
+            Err(ApplicationError::Domain(DomainError::Underflow))
+        ));
+
+        // other denoms are held next to it
+        let acc = storage.deposit(&address, &Token::new(5, "USDT")).await.unwrap();
+        assert_eq!(acc.balances.get("USDT"), Token::new(5, "USDT"));
+        assert_eq!(acc.balances.get("LOKI"), Token::new(60, "LOKI"));
+    }
 }
\ No newline at end of file
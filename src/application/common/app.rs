@@ -0,0 +1,77 @@
+use crate::application::common::context::{ExecutionContext, Stores};
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::Transaction;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Application running the operations of `[AppData]` addressed to it
+///
+/// Registered in `[AppRegistry]` under its name
+///
+/// [AppRegistry]: crate::application::common::app_router::AppRegistry
+#[async_trait]
+pub trait App: Send + Sync {
+    /// name in `[AppData::app]`; ex: `bank`
+    fn name(&self) -> &str;
+
+    /// operations accepted in `[AppData::operation]`; ex: `transfer`
+    fn operations(&self) -> &[&str];
+
+    /// Stateless checks of the operation and its payload, run before the transaction enters the mempool
+    fn validate(&self, data: &AppData) -> Result<(), ApplicationError>;
+
+    /// Tokens the operation takes from the sender besides the fee, `amount` is `[Transaction::amount]`
+    ///
+    /// Each token comes with the request field it is taken from, where its errors are reported;
+    /// ex: `body.amount`. The sender must hold them for the transaction to enter the mempool.
+    /// The amount is not checked at all by operations that leave it out
+    fn sender_debit(&self, _data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        Ok(vec![("body.amount", amount.clone())])
+    }
+
+    /// Runs the operation of `transaction.data` against the state of `ctx`
+    ///
+    /// On error the caller discards the state changes of `ctx`
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError>;
+
+    /// Answers the read-only `query` of the app; ex: `balance` with `{"address": "..."}`
+    async fn query(&self, query: &str, payload: &Value, stores: Stores<'_>) -> Result<Value, ApplicationError>;
+}
+
+/// Decodes an operation or query payload into its typed form
+///
+/// Payloads hold no integer above `u64::MAX`, their amounts are decimal strings, see `[decimal]`
+///
+/// [decimal]: crate::domain::models::token::decimal
+pub fn decode_payload<T: DeserializeOwned>(payload: &Value) -> Result<T, ApplicationError> {
+    T::deserialize(payload).map_err(|err| ApplicationError::InvalidData(
+        [("body.data.payload".to_string(), err.to_string())].into()
+    ))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::address::Address;
+    use crate::domain::models::signature::VerifyKey;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Payload {
+        receiver: Address
+    }
+
+    #[test]
+    fn test_decode_payload() {
+        let receiver = Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) };
+        let payload = serde_json::json!({ "receiver": receiver });
+        assert_eq!(decode_payload::<Payload>(&payload).unwrap().receiver, receiver);
+
+        let result = decode_payload::<Payload>(&serde_json::json!({ "receiver": 1 }));
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload")));
+    }
+}
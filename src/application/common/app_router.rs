@@ -1,4 +1,12 @@
+use crate::application::common::app::App;
+use crate::application::common::context::{ExecutionContext, Stores};
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::Transaction;
 use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
 
 /// The application router distributes transactions to target applications
 ///
@@ -7,12 +15,91 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait AppRouter: Send + Sync {
     async fn is_exist(&self, app_name: &str, operation: &str) -> bool;
+
+    /// Stateless checks of `data` by its app
+    async fn validate(&self, data: &AppData) -> Result<(), ApplicationError>;
+
+    /// Tokens the operation of `data` takes from the sender besides the fee, see `[App::sender_debit]`
+    async fn sender_debit(&self, data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError>;
+
+    /// Executes the operation carried in `transaction.data` within `ctx`
+    ///
+    /// On error the caller discards the state changes of `ctx`
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError>;
+
+    /// Answers a read-only query of `app_name`
+    async fn query(&self, app_name: &str, query: &str, payload: &Value) -> Result<Value, ApplicationError>;
+}
+
+/// `[AppRouter]` dispatching to the registered `[App]`s by name
+pub struct AppRegistry<'a> {
+    stores: Stores<'a>,
+    apps: HashMap<String, Box<dyn App + 'a>>
+}
+
+impl<'a> AppRegistry<'a> {
+    /// `stores` are read by the queries, operations see the state of their `[ExecutionContext]`
+    pub fn new(stores: Stores<'a>) -> Self {
+        AppRegistry { stores, apps: HashMap::new() }
+    }
+
+    /// Registers `app` under its name
+    ///
+    /// Panics if an app with the same name is already registered
+    pub fn register(&mut self, app: Box<dyn App + 'a>) {
+        let name = app.name().to_string();
+        assert!(!self.apps.contains_key(&name), "app {} is already registered", name);
+        self.apps.insert(name, app);
+    }
+
+    fn app(&self, app_name: &str, operation: &str) -> Result<&dyn App, ApplicationError> {
+        self.apps.get(app_name)
+            .filter(|app| app.operations().contains(&operation))
+            .map(|app| app.as_ref())
+            .ok_or_else(|| ApplicationError::NotFound(format!("{}/{}", app_name, operation)))
+    }
+}
+
+#[async_trait]
+impl AppRouter for AppRegistry<'_> {
+    async fn is_exist(&self, app_name: &str, operation: &str) -> bool {
+        self.app(app_name, operation).is_ok()
+    }
+
+    async fn validate(&self, data: &AppData) -> Result<(), ApplicationError> {
+        self.app(&data.app, &data.operation)?.validate(data)
+    }
+
+    async fn sender_debit(&self, data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        self.app(&data.app, &data.operation)?.sender_debit(data, amount)
+    }
+
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        self.app(&transaction.data.app, &transaction.data.operation)?
+            .execute(transaction, ctx)
+            .await
+    }
+
+    async fn query(&self, app_name: &str, query: &str, payload: &Value) -> Result<Value, ApplicationError> {
+        let app = self.apps.get(app_name)
+            .ok_or_else(|| ApplicationError::NotFound(format!("app {}", app_name)))?;
+        app.query(query, payload, self.stores).await
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::AppRouter;
-    use async_trait::async_trait;
+    use super::*;
+    use crate::application::common::acc_storage::AccStorage;
+    use crate::application::common::context::tests::MockStores;
+    use crate::application::common::app::decode_payload;
+    use crate::application::common::gas::{GasMeter, GasSchedule};
+    use crate::domain::models::address::Address;
+    use crate::domain::models::fee::Fee;
+    use crate::domain::models::hash::Hash;
+    use crate::domain::models::receipt::Event;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+    use serde::Deserialize;
 
     pub struct MockAppRouter;
 
@@ -21,9 +108,32 @@ pub mod tests {
         async fn is_exist(&self, app_name: &str, operation: &str) -> bool {
             app_name == "bank" && operation == "transfer"
         }
-    }
 
-    
+        async fn validate(&self, _data: &AppData) -> Result<(), ApplicationError> {
+            Ok(())
+        }
+
+        async fn sender_debit(&self, _data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+            Ok(vec![("body.amount", amount.clone())])
+        }
+
+        /// `bank`/`transfer` only debits the sender and emits a `transfer` event
+        async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+            if !self.is_exist(&transaction.data.app, &transaction.data.operation).await {
+                return Err(ApplicationError::NotFound(format!("{}/{}", transaction.data.app, transaction.data.operation)));
+            }
+            ctx.withdraw(&transaction.sender, &transaction.amount).await?;
+            ctx.emit(Event::new("bank", "transfer")
+                .with("sender", &transaction.sender)
+                .with("amount", transaction.amount.value)
+                .with("denom", &transaction.amount.denom));
+            Ok(())
+        }
+
+        async fn query(&self, app_name: &str, _query: &str, _payload: &Value) -> Result<Value, ApplicationError> {
+            Err(ApplicationError::NotFound(format!("app {}", app_name)))
+        }
+    }
 
     impl MockAppRouter {
         pub fn new() -> Self {
@@ -31,6 +141,70 @@ pub mod tests {
         }
     }
 
+    #[derive(Deserialize)]
+    struct MintPayload {
+        times: u8
+    }
+
+    /// `faucet`/`mint` credits the sender with the amount `times` times
+    struct FaucetApp;
+
+    #[async_trait]
+    impl App for FaucetApp {
+        fn name(&self) -> &str {
+            "faucet"
+        }
+
+        fn operations(&self) -> &[&str] {
+            &["mint"]
+        }
+
+        fn validate(&self, data: &AppData) -> Result<(), ApplicationError> {
+            let payload: MintPayload = decode_payload(&data.payload)?;
+            if payload.times == 0 {
+                return Err(ApplicationError::InvalidData(
+                    [("body.data.payload.times".to_string(), "must be greater than 0".to_string())].into()
+                ));
+            }
+            Ok(())
+        }
+
+        async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+            let payload: MintPayload = decode_payload(&transaction.data.payload)?;
+            for _ in 0..payload.times {
+                ctx.deposit(&transaction.sender, &transaction.amount).await?;
+            }
+            Ok(())
+        }
+
+        async fn query(&self, query: &str, payload: &Value, stores: Stores<'_>) -> Result<Value, ApplicationError> {
+            match query {
+                "nonce" => {
+                    let address: Address = decode_payload(payload)?;
+                    Ok(stores.accounts.get(&address).await.map_or(0, |acc| acc.nonce).into())
+                }
+                _ => Err(ApplicationError::NotFound(format!("query {}", query))),
+            }
+        }
+    }
+
+    fn address() -> Address {
+        Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) }
+    }
+
+    fn mint(app: &str, operation: &str, payload: Value) -> Transaction {
+        Transaction::new(
+            Hash([0; 32]),
+            address(),
+            AppData { app: app.to_string(), operation: operation.to_string(), payload },
+            Token::new(10, "LOKI"),
+            0,
+            Fee::new(100, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0; 64])
+        )
+    }
+
     #[tokio::test]
     async fn test_is_exist() {
         let router = MockAppRouter;
@@ -38,4 +212,44 @@ pub mod tests {
         assert!(!router.is_exist("bank", "deposit").await);
         assert!(!router.is_exist("wallet", "transfer").await);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_registry_dispatch() {
+        let mocks = MockStores::new();
+        let acc_storage = &mocks.accounts;
+        let mut registry = AppRegistry::new(mocks.stores());
+        registry.register(Box::new(FaucetApp));
+
+        assert!(registry.is_exist("faucet", "mint").await);
+        assert!(!registry.is_exist("faucet", "burn").await);
+        assert!(!registry.is_exist("bank", "mint").await);
+
+        assert!(registry.validate(&mint("faucet", "mint", serde_json::json!({ "times": 2 })).data).await.is_ok());
+        assert!(registry.validate(&mint("faucet", "mint", serde_json::json!({ "times": 0 })).data).await.is_err());
+        assert!(registry.validate(&mint("faucet", "mint", Value::Null).data).await.is_err());
+        assert!(matches!(
+            registry.validate(&mint("faucet", "burn", Value::Null).data).await,
+            Err(ApplicationError::NotFound(_))
+        ));
+
+        let mut ctx = ExecutionContext::new(mocks.stores(), GasMeter::new(100_000, GasSchedule::default()));
+        registry.execute(&mint("faucet", "mint", serde_json::json!({ "times": 2 })), &mut ctx).await.unwrap();
+        assert!(registry.execute(&mint("bank", "transfer", Value::Null), &mut ctx).await.is_err());
+        ctx.commit().await;
+        assert_eq!(acc_storage.get(&address()).await.unwrap().balances.get("LOKI").value, 20);
+
+        let nonce = registry.query("faucet", "nonce", &serde_json::to_value(address()).unwrap()).await.unwrap();
+        assert_eq!(nonce, Value::from(0));
+        assert!(registry.query("faucet", "supply", &Value::Null).await.is_err());
+        assert!(registry.query("bank", "nonce", &Value::Null).await.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "app faucet is already registered")]
+    fn test_register_twice() {
+        let mocks = MockStores::new();
+        let mut registry = AppRegistry::new(mocks.stores());
+        registry.register(Box::new(FaucetApp));
+        registry.register(Box::new(FaucetApp));
+    }
+}
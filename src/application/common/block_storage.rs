@@ -0,0 +1,43 @@
+use crate::domain::models::block::{Block, BlockHeader};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait BlockStorage: Send + Sync {
+    async fn get(&self, height: u64) -> Option<Block>;
+    async fn set(&self, block: Block);
+    /// Header of the highest block, `None` before the genesis block
+    async fn last(&self) -> Option<BlockHeader>;
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    pub struct MockBlockStorage {
+        blocks: Arc<RwLock<BTreeMap<u64, Block>>>
+    }
+
+    impl MockBlockStorage {
+        pub fn new() -> Self {
+            MockBlockStorage { blocks: Arc::new(RwLock::new(BTreeMap::new())) }
+        }
+    }
+
+    #[async_trait]
+    impl BlockStorage for MockBlockStorage {
+        async fn get(&self, height: u64) -> Option<Block> {
+            self.blocks.read().await.get(&height).cloned()
+        }
+
+        async fn set(&self, block: Block) {
+            self.blocks.write().await.insert(block.header.height, block);
+        }
+
+        async fn last(&self) -> Option<BlockHeader> {
+            self.blocks.read().await.values().next_back().map(|block| block.header.clone())
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::application::common::gas::GasSchedule;
+use crate::application::common::hasher::HashAlgorithm;
+use crate::domain::models::token::Token;
+use serde::Deserialize;
+
+/// Node wide validation settings
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NodeConfig {
+    /// Chain id every transaction must be signed for
+    pub chain_id: String,
+    /// Bech32 human readable part of the addresses of the chain
+    pub hrp: String,
+    /// Authenticate the sender of a recoverable signature by the key recovered from it
+    /// instead of verifying the signature against the sender key
+    pub recover_sender: bool,
+    /// How far past the next nonce of a sender a transaction may be queued
+    pub max_nonce_gap: u64,
+    /// Lowest accepted gas price per fee denom
+    ///
+    /// When it is not empty, fees can only be paid in the listed denoms
+    pub min_gas_prices: Vec<Token>,
+    /// Gas charged for the execution of transactions
+    pub gas_schedule: GasSchedule,
+    /// Algorithm of the transaction, block header, merkle and state hashes
+    pub hash_algorithm: HashAlgorithm
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        NodeConfig {
+            chain_id: "lokichain-1".to_string(),
+            hrp: "lokichain".to_string(),
+            recover_sender: false,
+            max_nonce_gap: 16,
+            min_gas_prices: Vec::new(),
+            gas_schedule: GasSchedule::default(),
+            hash_algorithm: HashAlgorithm::default()
+        }
+    }
+}
+
+impl NodeConfig {
+    /// Minimum gas price in `denom`, `None` if the denom is not accepted for fees
+    pub fn min_gas_price(&self, denom: &str) -> Option<Token> {
+        if self.min_gas_prices.is_empty() {
+            return Some(Token::zero(denom));
+        }
+        self.min_gas_prices.iter().find(|price| price.denom == denom).cloned()
+    }
+}
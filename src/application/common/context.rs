@@ -0,0 +1,313 @@
+use crate::application::common::acc_storage::AccStorage;
+use crate::application::common::denom_registry::DenomRegistry;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::gas::GasMeter;
+use crate::application::common::state_storage::StateStorage;
+use crate::domain::models::account::{Account, Balances};
+use crate::domain::models::address::Address;
+use crate::domain::models::denom::DenomMetadata;
+use crate::domain::models::receipt::Event;
+use crate::domain::models::token::Token;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Storages the app operations and queries run against
+#[derive(Clone, Copy)]
+pub struct Stores<'a> {
+    pub accounts: &'a dyn AccStorage,
+    pub state: &'a dyn StateStorage,
+    pub denoms: &'a dyn DenomRegistry
+}
+
+/// State of a single execution passed to the app operations
+///
+/// Every read and write of accounts, app state and denoms is charged to the `[GasMeter]`.
+/// Writes and emitted events are buffered and reach the `[Stores]` only on `[ExecutionContext::commit]`,
+/// dropping the context reverts all of them
+pub struct ExecutionContext<'a> {
+    stores: Stores<'a>,
+    pub meter: GasMeter,
+    /// height of the block the execution belongs to
+    pub height: u64,
+    /// timestamp of the block the execution belongs to
+    pub timestamp: u64,
+    writes: HashMap<Address, Account>,
+    /// `None` for a removed key
+    state_writes: HashMap<String, Option<Vec<u8>>>,
+    denom_writes: HashMap<String, DenomMetadata>,
+    events: Vec<Event>
+}
+
+impl<'a> ExecutionContext<'a> {
+    pub fn new(stores: Stores<'a>, meter: GasMeter) -> Self {
+        ExecutionContext {
+            stores,
+            meter,
+            height: 0,
+            timestamp: 0,
+            writes: HashMap::new(),
+            state_writes: HashMap::new(),
+            denom_writes: HashMap::new(),
+            events: Vec::new()
+        }
+    }
+
+    /// Runs the execution in the block at `height` and `timestamp`, the genesis position otherwise
+    pub fn at_block(mut self, height: u64, timestamp: u64) -> Self {
+        self.height = height;
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub async fn account(&mut self, address: &Address) -> Result<Option<Account>, ApplicationError> {
+        self.meter.charge_read()?;
+        match self.writes.get(address) {
+            Some(account) => Ok(Some(account.clone())),
+            None => Ok(self.stores.accounts.get(address).await),
+        }
+    }
+
+    pub fn set_account(&mut self, account: Account) -> Result<(), ApplicationError> {
+        self.meter.charge_write()?;
+        self.writes.insert(account.address.clone(), account);
+        Ok(())
+    }
+
+    /// App state under `key`, decoded from JSON
+    pub async fn state<T: DeserializeOwned>(&mut self, key: &str) -> Result<Option<T>, ApplicationError> {
+        self.meter.charge_read()?;
+        let value = match self.state_writes.get(key) {
+            Some(value) => value.clone(),
+            None => self.stores.state.get(key).await,
+        };
+        value.map(|bytes| serde_json::from_slice(&bytes).map_err(|err| anyhow::Error::from(err).into()))
+            .transpose()
+    }
+
+    pub fn set_state<T: Serialize>(&mut self, key: &str, value: &T) -> Result<(), ApplicationError> {
+        self.meter.charge_write()?;
+        let bytes = serde_json::to_vec(value).map_err(anyhow::Error::from)?;
+        self.state_writes.insert(key.to_string(), Some(bytes));
+        Ok(())
+    }
+
+    pub fn remove_state(&mut self, key: &str) -> Result<(), ApplicationError> {
+        self.meter.charge_write()?;
+        self.state_writes.insert(key.to_string(), None);
+        Ok(())
+    }
+
+    /// Metadata of the denom with the `base` unit
+    pub async fn denom(&mut self, base: &str) -> Result<Option<DenomMetadata>, ApplicationError> {
+        self.meter.charge_read()?;
+        match self.denom_writes.get(base) {
+            Some(metadata) => Ok(Some(metadata.clone())),
+            None => Ok(self.stores.denoms.get(base).await),
+        }
+    }
+
+    /// Registers a denom or replaces the metadata of `metadata.base`
+    ///
+    /// Fails if the metadata is not valid or one of its units is taken by another denom
+    pub async fn set_denom(&mut self, metadata: DenomMetadata) -> Result<(), ApplicationError> {
+        metadata.validate()?;
+        self.meter.charge_write()?;
+        for unit in [&metadata.base, &metadata.display] {
+            if let Some(owner) = self.unit_owner(unit).await {
+                if owner != metadata.base {
+                    return Err(ApplicationError::InvalidData(
+                        [("denom".to_string(), format!("{} is already registered", unit))].into()
+                    ));
+                }
+            }
+        }
+        self.denom_writes.insert(metadata.base.clone(), metadata);
+        Ok(())
+    }
+
+    /// Base unit of the denom using `unit` as its base or display unit
+    async fn unit_owner(&self, unit: &str) -> Option<String> {
+        let uses = |metadata: &DenomMetadata| metadata.base == unit || metadata.display == unit;
+        if let Some(metadata) = self.denom_writes.values().find(|metadata| uses(metadata)) {
+            return Some(metadata.base.clone());
+        }
+        // the registry may still hold the units of a denom replaced in this execution
+        self.stores.denoms.find_unit(unit).await
+            .filter(|metadata| !self.denom_writes.contains_key(&metadata.base))
+            .map(|metadata| metadata.base)
+    }
+
+    /// Same as `[AccStorage::deposit]`, within the execution
+    pub async fn deposit(&mut self, address: &Address, amount: &Token) -> Result<Account, ApplicationError> {
+        let mut acc = self.account(address).await?.unwrap_or_else(|| Account {
+            address: address.clone(),
+            nonce: 0,
+            balances: Balances::new()
+        });
+        acc.balances.add(amount)?;
+        self.set_account(acc.clone())?;
+        Ok(acc)
+    }
+
+    /// Same as `[AccStorage::withdraw]`, within the execution
+    pub async fn withdraw(&mut self, address: &Address, amount: &Token) -> Result<Account, ApplicationError> {
+        let mut acc = self.account(address).await?
+            .ok_or_else(|| ApplicationError::NotFound(format!("account {}", address)))?;
+        acc.balances.sub(amount)?;
+        self.set_account(acc.clone())?;
+        Ok(acc)
+    }
+
+    pub fn emit(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Writes the buffered changes to the stores and returns the emitted events
+    pub async fn commit(self) -> Vec<Event> {
+        for (address, account) in self.writes {
+            self.stores.accounts.set(address, account).await;
+        }
+        for (key, value) in self.state_writes {
+            match value {
+                Some(value) => self.stores.state.set(key, value).await,
+                None => self.stores.state.remove(&key).await,
+            }
+        }
+        for metadata in self.denom_writes.into_values() {
+            self.stores.denoms.set(metadata).await;
+        }
+        self.events
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::application::common::acc_storage::tests::MockAccStorage;
+    use crate::application::common::denom_registry::tests::MockDenomRegistry;
+    use crate::application::common::gas::GasSchedule;
+    use crate::application::common::state_storage::tests::MockStateStorage;
+    use crate::domain::models::signature::VerifyKey;
+
+    pub struct MockStores {
+        pub accounts: MockAccStorage,
+        pub state: MockStateStorage,
+        pub denoms: MockDenomRegistry
+    }
+
+    impl MockStores {
+        pub fn new() -> Self {
+            MockStores {
+                accounts: MockAccStorage::new(),
+                state: MockStateStorage::new(),
+                denoms: MockDenomRegistry::new()
+            }
+        }
+
+        pub fn stores(&self) -> Stores<'_> {
+            Stores { accounts: &self.accounts, state: &self.state, denoms: &self.denoms }
+        }
+    }
+
+    fn address(seed: u8) -> Address {
+        Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) }
+    }
+
+    fn meter(limit: u64) -> GasMeter {
+        GasMeter::new(limit, GasSchedule { read: 1, write: 2, payload_byte: 3, verify_signature: 4 })
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_revert() {
+        let mocks = MockStores::new();
+        let storage = &mocks.accounts;
+        storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(100));
+        ctx.withdraw(&address(1), &Token::new(30, "LOKI")).await.unwrap();
+        ctx.deposit(&address(2), &Token::new(30, "LOKI")).await.unwrap();
+        assert_eq!(ctx.meter.used(), 2 * (1 + 2));
+        // reads see the buffered writes, the storage does not
+        assert_eq!(ctx.account(&address(2)).await.unwrap().unwrap().balances.get("LOKI").value, 30);
+        assert!(storage.get(&address(2)).await.is_none());
+        drop(ctx);
+        assert!(storage.get(&address(2)).await.is_none());
+
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(100));
+        ctx.withdraw(&address(1), &Token::new(30, "LOKI")).await.unwrap();
+        ctx.deposit(&address(2), &Token::new(30, "LOKI")).await.unwrap();
+        ctx.emit(Event::new("bank", "transfer"));
+        assert_eq!(ctx.commit().await, vec![Event::new("bank", "transfer")]);
+        assert_eq!(storage.get(&address(1)).await.unwrap().balances.get("LOKI").value, 70);
+        assert_eq!(storage.get(&address(2)).await.unwrap().balances.get("LOKI").value, 30);
+    }
+
+    #[tokio::test]
+    async fn test_state_and_denoms() {
+        let mocks = MockStores::new();
+        mocks.state.set("test/removed".to_string(), b"1".to_vec()).await;
+        let points = DenomMetadata {
+            base: "upoints".to_string(),
+            display: "POINTS".to_string(),
+            decimals: 2,
+            symbol: "P".to_string()
+        };
+
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(100));
+        ctx.set_state("test/value", &u128::MAX).unwrap();
+        ctx.remove_state("test/removed").unwrap();
+        ctx.set_denom(points.clone()).await.unwrap();
+        assert_eq!(ctx.state::<u128>("test/value").await.unwrap(), Some(u128::MAX));
+        assert_eq!(ctx.state::<u128>("test/removed").await.unwrap(), None);
+        assert_eq!(ctx.denom("upoints").await.unwrap(), Some(points.clone()));
+        drop(ctx);
+        assert!(mocks.state.get("test/value").await.is_none());
+        assert!(mocks.state.get("test/removed").await.is_some());
+        assert!(mocks.denoms.get("upoints").await.is_none());
+
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(100));
+        ctx.set_state("test/value", &1u8).unwrap();
+        ctx.remove_state("test/removed").unwrap();
+        ctx.set_denom(points.clone()).await.unwrap();
+        ctx.commit().await;
+        assert_eq!(mocks.state.get("test/value").await, Some(b"1".to_vec()));
+        assert!(mocks.state.get("test/removed").await.is_none());
+        assert_eq!(mocks.denoms.get("upoints").await, Some(points.clone()));
+    }
+
+    #[tokio::test]
+    async fn test_denom_units() {
+        let mocks = MockStores::new();
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(100));
+        let usdt = mocks.denoms.get("uusdt").await.unwrap();
+
+        // units of another denom are taken
+        let shadowing = DenomMetadata { base: "upoints".to_string(), display: "USDT".to_string(), ..usdt.clone() };
+        assert!(ctx.set_denom(shadowing).await.is_err());
+
+        // a denom keeps its own units and frees the replaced ones
+        let renamed = DenomMetadata { display: "TETHER".to_string(), ..usdt.clone() };
+        ctx.set_denom(renamed).await.unwrap();
+        let points = DenomMetadata { base: "upoints".to_string(), display: "USDT".to_string(), ..usdt };
+        ctx.set_denom(points.clone()).await.unwrap();
+        let taken = DenomMetadata { base: "upts".to_string(), ..points };
+        assert!(ctx.set_denom(taken).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_out_of_gas() {
+        let mocks = MockStores::new();
+        mocks.accounts.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+
+        // enough for the read, not for the write
+        let mut ctx = ExecutionContext::new(mocks.stores(), meter(2));
+        assert!(matches!(
+            ctx.withdraw(&address(1), &Token::new(30, "LOKI")).await,
+            Err(ApplicationError::OutOfGas(2))
+        ));
+        assert_eq!(ctx.meter.used(), 2);
+        assert!(ctx.account(&address(1)).await.is_err());
+    }
+}
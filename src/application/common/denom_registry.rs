@@ -0,0 +1,144 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::denom::DenomMetadata;
+use crate::domain::models::token::Token;
+use async_trait::async_trait;
+
+/// Registry of the denominations known to the chain
+///
+/// Every `[Token]` denom in a transaction must be the `base` unit of a registered denom
+#[async_trait]
+pub trait DenomRegistry: Send + Sync {
+    /// Metadata by the base unit
+    async fn get(&self, base: &str) -> Option<DenomMetadata>;
+    /// Metadata by the base or the display unit
+    async fn find_unit(&self, unit: &str) -> Option<DenomMetadata>;
+    /// Fails if the metadata is not valid or one of its units is already taken
+    async fn register(&self, metadata: DenomMetadata) -> Result<(), ApplicationError>;
+    /// Inserts or replaces the metadata of `metadata.base` without any check
+    ///
+    /// Used to commit the denoms an `[ExecutionContext]` has already checked
+    ///
+    /// [ExecutionContext]: crate::application::common::context::ExecutionContext
+    async fn set(&self, metadata: DenomMetadata);
+
+    /// Parses an amount like `1.25 LOKI` into a base unit `[Token]`
+    async fn parse_amount(&self, amount: &str) -> Result<Token, ApplicationError> {
+        let (value, unit) = amount.trim().split_once(' ').ok_or_else(
+            || DomainError::InvalidAmount(format!("{}: expected `<value> <unit>`", amount))
+        )?;
+        let metadata = self.find_unit(unit).await
+            .ok_or_else(|| ApplicationError::NotFound(format!("denom {}", unit)))?;
+        Ok(metadata.to_token(value, unit)?)
+    }
+
+    /// Formats a base unit `[Token]` in its display unit
+    async fn format_amount(&self, token: &Token) -> Result<String, ApplicationError> {
+        let metadata = self.get(&token.denom).await
+            .ok_or_else(|| ApplicationError::NotFound(format!("denom {}", token.denom)))?;
+        Ok(metadata.format(token)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    /// Registry with `LOKI` (base unit, no decimals) and `USDT` (6 decimals of `uusdt`)
+    pub struct MockDenomRegistry {
+        denoms: Arc<RwLock<HashMap<String, DenomMetadata>>>
+    }
+
+    impl MockDenomRegistry {
+        pub fn new() -> Self {
+            let denoms = [
+                DenomMetadata {
+                    base: "LOKI".to_string(),
+                    display: "LOKI".to_string(),
+                    decimals: 0,
+                    symbol: "Ł".to_string()
+                },
+                DenomMetadata {
+                    base: "uusdt".to_string(),
+                    display: "USDT".to_string(),
+                    decimals: 6,
+                    symbol: "₮".to_string()
+                },
+            ];
+            MockDenomRegistry {
+                denoms: Arc::new(RwLock::new(
+                    denoms.into_iter().map(|denom| (denom.base.clone(), denom)).collect()
+                ))
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DenomRegistry for MockDenomRegistry {
+        async fn get(&self, base: &str) -> Option<DenomMetadata> {
+            self.denoms.read().await.get(base).cloned()
+        }
+
+        async fn find_unit(&self, unit: &str) -> Option<DenomMetadata> {
+            self.denoms.read().await
+                .values()
+                .find(|denom| denom.base == unit || denom.display == unit)
+                .cloned()
+        }
+
+        async fn register(&self, metadata: DenomMetadata) -> Result<(), ApplicationError> {
+            metadata.validate()?;
+            let mut denoms = self.denoms.write().await;
+            let taken = |unit: &str| denoms.values().any(|denom| denom.base == unit || denom.display == unit);
+            if taken(&metadata.base) || taken(&metadata.display) {
+                return Err(ApplicationError::InvalidData(
+                    [("denom".to_string(), format!("{} is already registered", metadata.base))].into()
+                ));
+            }
+            denoms.insert(metadata.base.clone(), metadata);
+            Ok(())
+        }
+
+        async fn set(&self, metadata: DenomMetadata) {
+            self.denoms.write().await.insert(metadata.base.clone(), metadata);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_format() {
+        let registry = MockDenomRegistry::new();
+
+        let token = registry.parse_amount("1.25 USDT").await.unwrap();
+        assert_eq!(token, Token::new(1_250_000, "uusdt"));
+        assert_eq!(registry.format_amount(&token).await.unwrap(), "1.25 USDT");
+        assert_eq!(registry.parse_amount("10 uusdt").await.unwrap(), Token::new(10, "uusdt"));
+        assert_eq!(registry.parse_amount("10 LOKI").await.unwrap(), Token::new(10, "LOKI"));
+
+        assert!(matches!(registry.parse_amount("1.25").await, Err(ApplicationError::Domain(_))));
+        assert!(matches!(registry.parse_amount("1.5 LOKI").await, Err(ApplicationError::Domain(_))));
+        assert!(matches!(registry.parse_amount("1 BTC").await, Err(ApplicationError::NotFound(_))));
+        assert!(matches!(registry.format_amount(&Token::new(1, "USDT")).await, Err(ApplicationError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register() {
+        let registry = MockDenomRegistry::new();
+        let btc = DenomMetadata {
+            base: "sat".to_string(),
+            display: "BTC".to_string(),
+            decimals: 8,
+            symbol: "₿".to_string()
+        };
+
+        registry.register(btc.clone()).await.unwrap();
+        assert_eq!(registry.get("sat").await, Some(btc.clone()));
+        assert_eq!(registry.find_unit("BTC").await, Some(btc.clone()));
+        assert!(registry.register(btc.clone()).await.is_err());
+
+        let shadowing = DenomMetadata { base: "USDT".to_string(), display: "USDT".to_string(), decimals: 0, ..btc };
+        assert!(registry.register(shadowing).await.is_err());
+    }
+}
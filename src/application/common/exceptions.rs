@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use thiserror::Error;
+use crate::domain::exceptions::DomainError;
 
 
 fn format_error_map(errors: &HashMap<String, String>) -> String {
@@ -20,10 +21,27 @@ pub enum ApplicationError {
     InvalidData(HashMap<String, String>),
     #[error("Not found: {0}")]
     NotFound(String),
+    #[error("Out of gas: limit {0}")]
+    OutOfGas(u64),
+    #[error(transparent)]
+    Domain(#[from] DomainError),
     #[error(transparent)]
     Other(#[from] anyhow::Error)
 }
 
+impl ApplicationError {
+    /// Stable code of the error kind, recorded in the receipts of reverted transactions
+    pub fn code(&self) -> u32 {
+        match self {
+            ApplicationError::InvalidData(_) => 1,
+            ApplicationError::NotFound(_) => 2,
+            ApplicationError::OutOfGas(_) => 3,
+            ApplicationError::Domain(_) => 4,
+            ApplicationError::Other(_) => 5,
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,130 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::signature::Signature;
+use crate::domain::services::encoding::Encode;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Gas charged for every kind of work an execution does
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GasSchedule {
+    /// account storage read
+    pub read: u64,
+    /// account storage write
+    pub write: u64,
+    /// byte of the canonically encoded `[AppData::payload]`
+    ///
+    /// [AppData::payload]: crate::domain::models::app_data::AppData::payload
+    pub payload_byte: u64,
+    /// single signature verification, a multisig pays for every member signature
+    pub verify_signature: u64
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        GasSchedule {
+            read: 1000,
+            write: 2000,
+            payload_byte: 10,
+            verify_signature: 1000
+        }
+    }
+}
+
+/// Counts the gas used by an execution against its gas limit
+///
+/// Running out of gas uses up the whole limit, so every next charge fails
+#[derive(Debug)]
+pub struct GasMeter {
+    limit: u64,
+    used: u64,
+    schedule: GasSchedule
+}
+
+impl GasMeter {
+    pub fn new(limit: u64, schedule: GasSchedule) -> Self {
+        GasMeter { limit, used: 0, schedule }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// Fails with `[ApplicationError::OutOfGas]` if `gas` is over the remaining gas
+    pub fn consume(&mut self, gas: u64) -> Result<(), ApplicationError> {
+        match self.used.checked_add(gas) {
+            Some(used) if used <= self.limit => {
+                self.used = used;
+                Ok(())
+            }
+            _ => {
+                self.used = self.limit;
+                Err(ApplicationError::OutOfGas(self.limit))
+            }
+        }
+    }
+
+    pub fn charge_read(&mut self) -> Result<(), ApplicationError> {
+        self.consume(self.schedule.read)
+    }
+
+    pub fn charge_write(&mut self) -> Result<(), ApplicationError> {
+        self.consume(self.schedule.write)
+    }
+
+    pub fn charge_payload(&mut self, payload: &Value) -> Result<(), ApplicationError> {
+        let mut buf = Vec::new();
+        payload.encode_to(&mut buf);
+        self.consume(self.schedule.payload_byte.saturating_mul(buf.len() as u64))
+    }
+
+    pub fn charge_signature(&mut self, signature: &Signature) -> Result<(), ApplicationError> {
+        let count = match signature {
+            Signature::Multisig(signatures) => signatures.len() as u64,
+            _ => 1,
+        };
+        self.consume(self.schedule.verify_signature.saturating_mul(count))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> GasSchedule {
+        GasSchedule { read: 1, write: 2, payload_byte: 3, verify_signature: 4 }
+    }
+
+    #[test]
+    fn test_charges() {
+        let mut meter = GasMeter::new(100, schedule());
+        meter.charge_read().unwrap();
+        meter.charge_write().unwrap();
+        // tag byte and 8 bytes of the integer
+        meter.charge_payload(&serde_json::json!(7)).unwrap();
+        meter.charge_signature(&Signature::Ed25519([0; 64])).unwrap();
+        meter.charge_signature(&Signature::Multisig(vec![
+            (0, Signature::Ed25519([0; 64])),
+            (1, Signature::Secp256k1([0; 65])),
+        ])).unwrap();
+        assert_eq!(meter.used(), 1 + 2 + 3 * 9 + 4 + 8);
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let mut meter = GasMeter::new(10, schedule());
+        meter.consume(9).unwrap();
+        assert!(matches!(meter.charge_write(), Err(ApplicationError::OutOfGas(10))));
+        assert_eq!(meter.used(), 10);
+        assert!(meter.charge_read().is_err());
+
+        let mut meter = GasMeter::new(10, schedule());
+        assert!(meter.consume(u64::MAX).is_err());
+        assert_eq!(meter.used(), 10);
+    }
+}
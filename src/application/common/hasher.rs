@@ -1,5 +1,37 @@
 use async_trait::async_trait;
 use crate::domain::models::hash::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Hash algorithm selected in the node configuration; ex: `"sha256"`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+/// Hashing domain
+///
+/// Every domain has its own tag, so hashes of different kinds of data can never collide
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashDomain {
+    Transaction,
+    BlockHeader,
+    MerkleNode,
+    StateNode,
+}
+
+impl HashDomain {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            HashDomain::Transaction => "lokichain/transaction/v1",
+            HashDomain::BlockHeader => "lokichain/block-header/v1",
+            HashDomain::MerkleNode => "lokichain/merkle-node/v1",
+            HashDomain::StateNode => "lokichain/state-node/v1",
+        }
+    }
+}
 
 #[async_trait]
 pub trait Hasher: Send + Sync {
@@ -11,6 +43,7 @@ pub trait Hasher: Send + Sync {
 pub mod tests {
     use super::*;
     use crate::domain::models::hash::Hash;
+    use sha2::{Digest, Sha256};
 
     pub struct MockHasher;
 
@@ -31,6 +64,20 @@ pub mod tests {
         }
     }
 
+    /// Untagged SHA-256, for tests that need a collision resistant hasher
+    pub struct PlainSha256Hasher;
+
+    #[async_trait]
+    impl Hasher for PlainSha256Hasher {
+        async fn hash(&self, value: &[u8]) -> Hash {
+            Hash(Sha256::digest(value).into())
+        }
+
+        async fn verify(&self, value: &[u8], hash: &Hash) -> bool {
+            self.hash(value).await == *hash
+        }
+    }
+
     #[tokio::test]
     async fn test_hasher() {
         let hasher = MockHasher;
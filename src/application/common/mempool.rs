@@ -1,12 +1,23 @@
 use async_trait::async_trait;
+use crate::domain::models::address::Address;
 use crate::domain::models::hash::Hash;
 use crate::domain::models::transaction::{Transaction, TransactionWithState};
 
 #[async_trait]
 pub trait MemPool: Send + Sync {
+    /// Adds the transaction, or replaces the one with the same hash
     async fn add(&self, transaction: TransactionWithState);
     async fn get(&self, hash: &Hash) -> Option<TransactionWithState>;
+    /// Pending and queued transactions of the sender
+    async fn by_sender(&self, sender: &Address) -> Vec<TransactionWithState>;
+    /// Removes and returns up to `limit` pending transactions in nonce order,
+    /// queued transactions stay in the pool
     async fn release(&self, limit: usize) -> Vec<TransactionWithState>;
+    /// Removes and returns the transactions whose validity window ends before the block at `height` and `timestamp`
+    ///
+    /// The pending transactions of a sender that follow an evicted pending one go back to `Queued`,
+    /// they can not execute until its nonce is used again
+    async fn evict_expired(&self, height: u64, timestamp: u64) -> Vec<TransactionWithState>;
     async fn count(&self) -> usize;
 }
 
@@ -17,9 +28,11 @@ pub mod tests {
     use tokio::sync::RwLock;
     use crate::domain::models::address::Address;
     use crate::domain::models::app_data::AppData;
+    use crate::domain::models::fee::Fee;
     use crate::domain::models::signature::{Signature, VerifyKey};
     use crate::domain::models::token::Token;
     use crate::domain::models::transaction::TxState;
+    use crate::domain::models::validity::{ValidityBound, ValidityWindow};
     use super::*;
 
     pub struct MockMemPool {
@@ -42,14 +55,27 @@ pub mod tests {
             self.transactions.read().await.get(hash).cloned()
         }
 
+        async fn by_sender(&self, sender: &Address) -> Vec<TransactionWithState> {
+            self.transactions.read().await
+                .values()
+                .filter(|tx| tx.transaction.sender == *sender)
+                .cloned()
+                .collect()
+        }
+
         async fn release(&self, limit: usize) -> Vec<TransactionWithState> {
             let mut transactions = Vec::with_capacity(limit);
             let mut tx_guard = self.transactions.write().await;
 
-            let hashes_to_remove: Vec<Hash> = tx_guard
-                .keys()
+            let mut pending: Vec<&TransactionWithState> = tx_guard
+                .values()
+                .filter(|tx| tx.state == TxState::PendingConfirmation)
+                .collect();
+            pending.sort_by_key(|tx| tx.transaction.nonce);
+            let hashes_to_remove: Vec<Hash> = pending
+                .into_iter()
                 .take(limit)
-                .cloned()
+                .map(|tx| tx.transaction.hash.clone())
                 .collect();
 
             for hash in hashes_to_remove {
@@ -61,6 +87,28 @@ pub mod tests {
             transactions
         }
 
+        async fn evict_expired(&self, height: u64, timestamp: u64) -> Vec<TransactionWithState> {
+            let mut tx_guard = self.transactions.write().await;
+            let expired: Vec<Hash> = tx_guard
+                .values()
+                .filter(|tx| tx.transaction.validity().is_expired(height, timestamp))
+                .map(|tx| tx.transaction.hash.clone())
+                .collect();
+            let evicted: Vec<TransactionWithState> = expired.iter().filter_map(|hash| tx_guard.remove(hash)).collect();
+
+            for gap in evicted.iter().filter(|tx| tx.state == TxState::PendingConfirmation) {
+                for tx in tx_guard.values_mut() {
+                    if tx.state == TxState::PendingConfirmation
+                        && tx.transaction.sender == gap.transaction.sender
+                        && tx.transaction.nonce > gap.transaction.nonce {
+                        tx.state = TxState::Queued;
+                    }
+                }
+            }
+
+            evicted
+        }
+
         async fn count(&self) -> usize {
             self.transactions.read().await.len()
         }
@@ -74,7 +122,7 @@ pub mod tests {
             hash.clone(),
             Address {
                 network: "lokichain".to_string(),
-                vk: VerifyKey([0; 32])
+                vk: VerifyKey::Ed25519([0; 32])
             },
             AppData {
                 app: "bank".to_string(),
@@ -85,9 +133,10 @@ pub mod tests {
                 value: 10,
                 denom: "LOKI".to_string()
             },
-            10,
             0,
-            Signature([0u8; 64])
+            Fee::new(10, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0u8; 64])
         );
 
         let state = TxState::PendingConfirmation;
@@ -99,4 +148,68 @@ pub mod tests {
         mempool.release(1).await;
         assert_eq!(mempool.count().await, 0);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_evict_expired() {
+        let mempool = MockMemPool::new();
+        let make = |seed: u8, valid_until: Option<ValidityBound>| Transaction::new(
+            Hash([seed; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([0; 32]) },
+            AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: serde_json::Value::Null },
+            Token::new(10, "LOKI"),
+            0,
+            Fee::new(10, Token::new(1, "LOKI")),
+            seed as u64,
+            Signature::Ed25519([0u8; 64])
+        ).with_validity(ValidityWindow { valid_after: None, valid_until });
+
+        mempool.add(TransactionWithState::new(&make(0, None), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(1, Some(ValidityBound::Height(5))), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(2, Some(ValidityBound::Timestamp(1000))), TxState::Queued)).await;
+
+        assert!(mempool.evict_expired(5, 1000).await.is_empty());
+        let evicted = mempool.evict_expired(6, 1000).await;
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].transaction.hash, Hash([1; 32]));
+        assert_eq!(mempool.evict_expired(6, 1001).await.len(), 1);
+        assert_eq!(mempool.count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_evict_expired_requeues_successors() {
+        let mempool = MockMemPool::new();
+        let make = |seed: u8, nonce: u64, valid_until: Option<ValidityBound>| Transaction::new(
+            Hash([seed; 32]),
+            Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed / 10; 32]) },
+            AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: serde_json::Value::Null },
+            Token::new(10, "LOKI"),
+            0,
+            Fee::new(10, Token::new(1, "LOKI")),
+            nonce,
+            Signature::Ed25519([0u8; 64])
+        ).with_validity(ValidityWindow { valid_after: None, valid_until });
+
+        // sender 0 has nonces 0..3 pending, nonce 1 expires; sender 1 has nonce 0 pending
+        mempool.add(TransactionWithState::new(&make(0, 0, None), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(1, 1, Some(ValidityBound::Height(5))), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(2, 2, None), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(3, 3, None), TxState::PendingConfirmation)).await;
+        mempool.add(TransactionWithState::new(&make(10, 0, None), TxState::PendingConfirmation)).await;
+
+        assert_eq!(mempool.evict_expired(6, 0).await.len(), 1);
+        let state = |seed: u8| {
+            let mempool = &mempool;
+            async move { mempool.get(&Hash([seed; 32])).await.unwrap().state }
+        };
+        assert_eq!(state(0).await, TxState::PendingConfirmation);
+        assert_eq!(state(2).await, TxState::Queued);
+        assert_eq!(state(3).await, TxState::Queued);
+        assert_eq!(state(10).await, TxState::PendingConfirmation);
+
+        let released = mempool.release(10).await;
+        let mut released = released.into_iter().map(|tx| tx.transaction.hash.0[0]).collect::<Vec<_>>();
+        released.sort();
+        assert_eq!(released, vec![0, 10]);
+        assert_eq!(mempool.count().await, 2);
+    }
+}
@@ -2,7 +2,16 @@ pub mod interactor;
 pub mod hasher;
 pub mod exceptions;
 pub mod mempool;
+pub mod app;
 pub mod app_router;
 pub mod signer;
 pub mod acc_storage;
 pub mod tx_storage;
+pub mod config;
+pub mod denom_registry;
+pub mod gas;
+pub mod context;
+pub mod block_storage;
+pub mod clock;
+pub mod receipt_storage;
+pub mod state_storage;
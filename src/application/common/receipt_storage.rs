@@ -0,0 +1,39 @@
+use crate::domain::models::hash::Hash;
+use crate::domain::models::receipt::Receipt;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ReceiptStorage: Send + Sync {
+    /// Receipt of the transaction with `tx_hash`
+    async fn get(&self, tx_hash: &Hash) -> Option<Receipt>;
+    async fn set(&self, receipt: Receipt);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    pub struct MockReceiptStorage {
+        receipts: Arc<RwLock<HashMap<Hash, Receipt>>>
+    }
+
+    impl MockReceiptStorage {
+        pub fn new() -> Self {
+            MockReceiptStorage { receipts: Arc::new(RwLock::new(HashMap::new())) }
+        }
+    }
+
+    #[async_trait]
+    impl ReceiptStorage for MockReceiptStorage {
+        async fn get(&self, tx_hash: &Hash) -> Option<Receipt> {
+            self.receipts.read().await.get(tx_hash).cloned()
+        }
+
+        async fn set(&self, receipt: Receipt) {
+            self.receipts.write().await.insert(receipt.tx_hash.clone(), receipt);
+        }
+    }
+}
@@ -4,7 +4,10 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait Signer: Send + Sync {
     async fn sign(&self, data: &[u8], sign_key: &SignKey) -> Signature;
+    async fn verify_key(&self, sign_key: &SignKey) -> VerifyKey;
     async fn verify(&self, data: &[u8], signature: &Signature, verify_key: &VerifyKey) -> bool;
+    /// Recovers the `[VerifyKey]` from the signature, `None` if the scheme does not support it
+    async fn recover(&self, data: &[u8], signature: &Signature) -> Option<VerifyKey>;
 }
 
 #[cfg(test)]
@@ -14,7 +17,8 @@ pub mod tests {
 
     /// Simple mock signer implementation
     ///
-    /// For verification to work correctly, `[SignKey]` must be equal to `[VerifyKey]`
+    /// For verification to work correctly, `[SignKey]` must be equal to `[VerifyKey]`.
+    /// Only Ed25519 keys are supported
     pub struct MockSigner;
 
     #[async_trait]
@@ -23,22 +27,33 @@ pub mod tests {
             let mut signature = [0u8; 64];
             let data_len = std::cmp::min(32, data.len());
             signature[..data_len].copy_from_slice(&data[..data_len]);
-            signature[32..].copy_from_slice(&sign_key.0);
-            Signature(signature)
+            signature[32..].copy_from_slice(sign_key.as_bytes());
+            Signature::Ed25519(signature)
+        }
+
+        async fn verify_key(&self, sign_key: &SignKey) -> VerifyKey {
+            VerifyKey::Ed25519(sign_key.as_bytes().try_into().unwrap())
         }
 
         async fn verify(&self, data: &[u8], signature: &Signature, verify_key: &VerifyKey) -> bool {
-            let check_sign = self.sign(data, &SignKey(verify_key.0)).await;
+            let VerifyKey::Ed25519(vk) = verify_key else {
+                return false;
+            };
+            let check_sign = self.sign(data, &SignKey::Ed25519(*vk)).await;
             check_sign == *signature
         }
+
+        async fn recover(&self, _data: &[u8], _signature: &Signature) -> Option<VerifyKey> {
+            None
+        }
     }
 
     #[tokio::test]
     async fn test_signer() {
         let signer = MockSigner;
         let data = b"hello world";
-        let sign_key = SignKey([0u8; 32]);
-        let verify_key = VerifyKey([0u8; 32]);
+        let sign_key = SignKey::Ed25519([0u8; 32]);
+        let verify_key = VerifyKey::Ed25519([0u8; 32]);
         let signature = signer.sign(data, &sign_key).await;
         assert!(signer.verify(data, &signature, &verify_key).await);
     }
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+
+/// Key-value state of the apps
+///
+/// Keys are prefixed with the app name; ex: `token/denoms/<denom>`.
+/// Apps read and write it only through `[ExecutionContext]`
+///
+/// [ExecutionContext]: crate::application::common::context::ExecutionContext
+#[async_trait]
+pub trait StateStorage: Send + Sync {
+    async fn get(&self, key: &str) -> Option<Vec<u8>>;
+    async fn set(&self, key: String, value: Vec<u8>);
+    async fn remove(&self, key: &str);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    pub struct MockStateStorage {
+        values: Arc<RwLock<BTreeMap<String, Vec<u8>>>>
+    }
+
+    impl MockStateStorage {
+        pub fn new() -> Self {
+            MockStateStorage { values: Arc::new(RwLock::new(BTreeMap::new())) }
+        }
+    }
+
+    #[async_trait]
+    impl StateStorage for MockStateStorage {
+        async fn get(&self, key: &str) -> Option<Vec<u8>> {
+            self.values.read().await.get(key).cloned()
+        }
+
+        async fn set(&self, key: String, value: Vec<u8>) {
+            self.values.write().await.insert(key, value);
+        }
+
+        async fn remove(&self, key: &str) {
+            self.values.write().await.remove(key);
+        }
+    }
+}
@@ -13,6 +13,7 @@ pub trait TxStorage: Send + Sync {
 pub mod tests {
     use super::*;
     use crate::domain::models::app_data::AppData;
+    use crate::domain::models::fee::Fee;
     use crate::domain::models::signature::{Signature, VerifyKey};
     use crate::domain::models::token::Token;
     use std::collections::HashMap;
@@ -52,7 +53,7 @@ pub mod tests {
             hash.clone(),
             Address {
                 network: "lokichain".to_string(),
-                vk: VerifyKey([1; 32])
+                vk: VerifyKey::Ed25519([1; 32])
             },
             AppData {
                 app: "bank".to_string(),
@@ -60,9 +61,10 @@ pub mod tests {
                 payload: serde_json::Value::Null
             },
             Token { value: 100, denom: "LOKI".to_string() },
-            10,
             0,
-            Signature([0; 64])
+            Fee::new(10, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0; 64])
         );
         storage.set(hash.clone(), tx).await;
         let result = storage.get(&hash).await.unwrap();
@@ -1,3 +1,6 @@
 pub mod common;
 pub mod transaction;
-pub mod bank;
\ No newline at end of file
+pub mod bank;
+pub mod token;
+pub mod block;
+pub mod state;
\ No newline at end of file
@@ -0,0 +1,479 @@
+//! Authenticated state
+//!
+//! Sparse merkle tree of 256 levels over three keyed namespaces, each with its own key and value prefix
+//! so their leaves never collide:
+//!
+//! - accounts: key `H(0x02 || address)`, value `H(0x03 || account)`
+//! - app state entries of `[StateStorage]`: key `H(0x04 || key)`, value `H(0x05 || bytes)`
+//! - denoms of `[DenomRegistry]`: key `H(0x06 || base)`, value `H(0x07 || metadata)`
+//!
+//! Leaves are hashed as `H(0x00 || key || value)` and nodes as `H(0x01 || left || right)`.
+//! To keep the tree compact, an empty subtree is 32 zero bytes and a subtree
+//! with a single leaf is that leaf itself, so leaves sit at the shortest unique prefix of their key.
+//!
+//! The `[Hasher]` is expected to be tagged with `[HashDomain::StateNode]`
+//!
+//! [HashDomain::StateNode]: crate::application::common::hasher::HashDomain::StateNode
+//! [StateStorage]: crate::application::common::state_storage::StateStorage
+//! [DenomRegistry]: crate::application::common::denom_registry::DenomRegistry
+
+use crate::application::common::hasher::Hasher;
+use crate::domain::models::account::Account;
+use crate::domain::models::address::Address;
+use crate::domain::models::denom::DenomMetadata;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::state_proof::{StateLeaf, StateProof};
+use crate::domain::services::encoding::encode;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+const ACCOUNT_KEY_PREFIX: u8 = 0x02;
+const ACCOUNT_VALUE_PREFIX: u8 = 0x03;
+const ENTRY_KEY_PREFIX: u8 = 0x04;
+const ENTRY_VALUE_PREFIX: u8 = 0x05;
+const DENOM_KEY_PREFIX: u8 = 0x06;
+const DENOM_VALUE_PREFIX: u8 = 0x07;
+
+/// Hash of an empty subtree
+const EMPTY: Hash = Hash([0; 32]);
+
+/// Depth of the tree, one level per key bit
+const DEPTH: usize = 256;
+
+type HashFuture<'a> = Pin<Box<dyn Future<Output = Hash> + Send + 'a>>;
+
+async fn prefixed(hasher: &dyn Hasher, prefix: u8, bytes: &[u8]) -> Hash {
+    hasher.hash(&[&[prefix], bytes].concat()).await
+}
+
+async fn state_key(hasher: &dyn Hasher, address: &Address) -> Hash {
+    prefixed(hasher, ACCOUNT_KEY_PREFIX, &encode(address)).await
+}
+
+async fn value_hash(hasher: &dyn Hasher, account: &Account) -> Hash {
+    prefixed(hasher, ACCOUNT_VALUE_PREFIX, &encode(account)).await
+}
+
+async fn entry_key(hasher: &dyn Hasher, key: &str) -> Hash {
+    prefixed(hasher, ENTRY_KEY_PREFIX, &encode(key)).await
+}
+
+async fn entry_value_hash(hasher: &dyn Hasher, value: &[u8]) -> Hash {
+    prefixed(hasher, ENTRY_VALUE_PREFIX, &encode(value)).await
+}
+
+async fn denom_key(hasher: &dyn Hasher, base: &str) -> Hash {
+    prefixed(hasher, DENOM_KEY_PREFIX, &encode(base)).await
+}
+
+async fn denom_value_hash(hasher: &dyn Hasher, metadata: &DenomMetadata) -> Hash {
+    prefixed(hasher, DENOM_VALUE_PREFIX, &encode(metadata)).await
+}
+
+async fn leaf_hash(hasher: &dyn Hasher, leaf: &StateLeaf) -> Hash {
+    hasher.hash(&[&[LEAF_PREFIX], leaf.key.0.as_slice(), leaf.value_hash.0.as_slice()].concat()).await
+}
+
+async fn node_hash(hasher: &dyn Hasher, left: &Hash, right: &Hash) -> Hash {
+    hasher.hash(&[&[NODE_PREFIX], left.0.as_slice(), right.0.as_slice()].concat()).await
+}
+
+/// Bit of the key at `depth`, most significant first
+fn bit(key: &[u8; 32], depth: usize) -> bool {
+    key[depth / 8] >> (7 - depth % 8) & 1 == 1
+}
+
+fn common_prefix(first: &[u8; 32], second: &[u8; 32]) -> usize {
+    (0..DEPTH).find(|depth| bit(first, *depth) != bit(second, *depth)).unwrap_or(DEPTH)
+}
+
+/// Hash of the subtree at `depth` holding `leaves`, sorted by key
+fn subtree<'a>(hasher: &'a dyn Hasher, leaves: &'a [(StateLeaf, Hash)], depth: usize) -> HashFuture<'a> {
+    Box::pin(async move {
+        match leaves {
+            [] => EMPTY,
+            [(_, hash)] => hash.clone(),
+            _ => {
+                let split = leaves.partition_point(|(leaf, _)| !bit(&leaf.key.0, depth));
+                let left = subtree(hasher, &leaves[..split], depth + 1).await;
+                let right = subtree(hasher, &leaves[split..], depth + 1).await;
+                node_hash(hasher, &left, &right).await
+            }
+        }
+    })
+}
+
+/// In memory state tree over accounts, app state entries and denoms
+pub struct StateTree<'a> {
+    hasher: &'a dyn Hasher,
+    /// key -> value hash
+    leaves: BTreeMap<[u8; 32], Hash>
+}
+
+impl<'a> StateTree<'a> {
+    pub fn new(hasher: &'a dyn Hasher) -> Self {
+        StateTree { hasher, leaves: BTreeMap::new() }
+    }
+
+    pub async fn from_accounts(hasher: &'a dyn Hasher, accounts: &[Account]) -> Self {
+        let mut tree = StateTree::new(hasher);
+        for account in accounts {
+            tree.set(account).await;
+        }
+        tree
+    }
+
+    /// Inserts or updates the account at its address
+    pub async fn set(&mut self, account: &Account) {
+        let key = state_key(self.hasher, &account.address).await;
+        let value = value_hash(self.hasher, account).await;
+        self.leaves.insert(key.0, value);
+    }
+
+    pub async fn remove(&mut self, address: &Address) {
+        let key = state_key(self.hasher, address).await;
+        self.leaves.remove(&key.0);
+    }
+
+    /// Inserts or updates the app state entry at `key`
+    pub async fn set_entry(&mut self, key: &str, value: &[u8]) {
+        let key = entry_key(self.hasher, key).await;
+        let value = entry_value_hash(self.hasher, value).await;
+        self.leaves.insert(key.0, value);
+    }
+
+    pub async fn remove_entry(&mut self, key: &str) {
+        let key = entry_key(self.hasher, key).await;
+        self.leaves.remove(&key.0);
+    }
+
+    /// Inserts or updates the denom at its base unit
+    pub async fn set_denom(&mut self, metadata: &DenomMetadata) {
+        let key = denom_key(self.hasher, &metadata.base).await;
+        let value = denom_value_hash(self.hasher, metadata).await;
+        self.leaves.insert(key.0, value);
+    }
+
+    /// Leaves with their hashes in key order
+    async fn leaf_hashes(&self) -> Vec<(StateLeaf, Hash)> {
+        let mut leaves = Vec::with_capacity(self.leaves.len());
+        for (key, value_hash) in &self.leaves {
+            let leaf = StateLeaf { key: Hash(*key), value_hash: value_hash.clone() };
+            let hash = leaf_hash(self.hasher, &leaf).await;
+            leaves.push((leaf, hash));
+        }
+        leaves
+    }
+
+    /// Root committed as `[BlockHeader::state_root]`
+    ///
+    /// [BlockHeader::state_root]: crate::domain::models::block::BlockHeader::state_root
+    pub async fn root(&self) -> Hash {
+        subtree(self.hasher, &self.leaf_hashes().await, 0).await
+    }
+
+    /// Proves that the account at `address` is in the tree, or that it is absent
+    pub async fn prove(&self, address: &Address) -> StateProof {
+        self.prove_key(&state_key(self.hasher, address).await).await
+    }
+
+    /// Proves that the app state entry at `key` is in the tree, or that it is absent
+    pub async fn prove_entry(&self, key: &str) -> StateProof {
+        self.prove_key(&entry_key(self.hasher, key).await).await
+    }
+
+    /// Proves that the denom with `base` unit is in the tree, or that it is absent
+    pub async fn prove_denom(&self, base: &str) -> StateProof {
+        self.prove_key(&denom_key(self.hasher, base).await).await
+    }
+
+    async fn prove_key(&self, key: &Hash) -> StateProof {
+        let leaves = self.leaf_hashes().await;
+
+        let mut path = leaves.as_slice();
+        let mut siblings = Vec::new();
+        while path.len() > 1 {
+            let depth = siblings.len();
+            let split = path.partition_point(|(leaf, _)| !bit(&leaf.key.0, depth));
+            let (own, other) = if bit(&key.0, depth) {
+                (&path[split..], &path[..split])
+            } else {
+                (&path[..split], &path[split..])
+            };
+            siblings.push(subtree(self.hasher, other, depth + 1).await);
+            path = own;
+        }
+
+        StateProof {
+            leaf: path.first().map(|(leaf, _)| leaf.clone()),
+            siblings
+        }
+    }
+}
+
+/// Checks the proof against the state `root`
+///
+/// With `Some(account)` checks that the account is stored at `address`,
+/// with `None` checks that there is no account at `address`
+pub async fn verify_account(
+    hasher: &dyn Hasher,
+    root: &Hash,
+    address: &Address,
+    account: Option<&Account>,
+    proof: &StateProof
+) -> bool {
+    let value = match account {
+        Some(account) if account.address != *address => return false,
+        Some(account) => Some(value_hash(hasher, account).await),
+        None => None,
+    };
+    verify_key(hasher, root, &state_key(hasher, address).await, value, proof).await
+}
+
+/// Checks the proof of the app state entry at `key` against the state `root`, `None` for an absent entry
+pub async fn verify_entry(
+    hasher: &dyn Hasher,
+    root: &Hash,
+    key: &str,
+    value: Option<&[u8]>,
+    proof: &StateProof
+) -> bool {
+    let value = match value {
+        Some(value) => Some(entry_value_hash(hasher, value).await),
+        None => None,
+    };
+    verify_key(hasher, root, &entry_key(hasher, key).await, value, proof).await
+}
+
+/// Checks the proof of the denom with `base` unit against the state `root`, `None` for an absent denom
+pub async fn verify_denom(
+    hasher: &dyn Hasher,
+    root: &Hash,
+    base: &str,
+    metadata: Option<&DenomMetadata>,
+    proof: &StateProof
+) -> bool {
+    let value = match metadata {
+        Some(metadata) if metadata.base != base => return false,
+        Some(metadata) => Some(denom_value_hash(hasher, metadata).await),
+        None => None,
+    };
+    verify_key(hasher, root, &denom_key(hasher, base).await, value, proof).await
+}
+
+async fn verify_key(
+    hasher: &dyn Hasher,
+    root: &Hash,
+    key: &Hash,
+    value_hash: Option<Hash>,
+    proof: &StateProof
+) -> bool {
+    if proof.siblings.len() > DEPTH {
+        return false;
+    }
+
+    let mut hash = match (&proof.leaf, value_hash) {
+        (Some(leaf), Some(value_hash)) => {
+            if leaf.key != *key || leaf.value_hash != value_hash {
+                return false;
+            }
+            leaf_hash(hasher, leaf).await
+        }
+        // another key that shares the path, the key itself would be stored in its place
+        (Some(leaf), None) => {
+            if leaf.key == *key || common_prefix(&leaf.key.0, &key.0) < proof.siblings.len() {
+                return false;
+            }
+            leaf_hash(hasher, leaf).await
+        }
+        (None, Some(_)) => return false,
+        (None, None) => EMPTY,
+    };
+
+    for (depth, sibling) in proof.siblings.iter().enumerate().rev() {
+        hash = if bit(&key.0, depth) {
+            node_hash(hasher, sibling, &hash).await
+        } else {
+            node_hash(hasher, &hash, sibling).await
+        };
+    }
+
+    hash == *root
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::hasher::tests::PlainSha256Hasher;
+    use crate::domain::models::signature::VerifyKey;
+    use crate::domain::models::token::Token;
+
+    fn make_account(seed: u8, balance: u128) -> Account {
+        Account {
+            address: Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) },
+            nonce: 0,
+            balances: Token { value: balance, denom: "LOKI".to_string() }.into()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_tree() {
+        let tree = StateTree::new(&PlainSha256Hasher);
+        let root = tree.root().await;
+        assert_eq!(root, EMPTY);
+
+        let account = make_account(1, 100);
+        let proof = tree.prove(&account.address).await;
+        assert!(proof.leaf.is_none() && proof.siblings.is_empty());
+        assert!(verify_account(&PlainSha256Hasher, &root, &account.address, None, &proof).await);
+        assert!(!verify_account(&PlainSha256Hasher, &root, &account.address, Some(&account), &proof).await);
+    }
+
+    #[tokio::test]
+    async fn test_membership() {
+        let accounts = (0..20).map(|seed| make_account(seed, seed as u128 * 10)).collect::<Vec<_>>();
+        let tree = StateTree::from_accounts(&PlainSha256Hasher, &accounts).await;
+        let root = tree.root().await;
+
+        for account in &accounts {
+            let proof = tree.prove(&account.address).await;
+            assert!(verify_account(&PlainSha256Hasher, &root, &account.address, Some(account), &proof).await);
+            assert!(!verify_account(&PlainSha256Hasher, &root, &account.address, None, &proof).await);
+
+            let mut forged = account.clone();
+            forged.balances.add(&Token::new(1, "LOKI")).unwrap();
+            assert!(!verify_account(&PlainSha256Hasher, &root, &account.address, Some(&forged), &proof).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_membership() {
+        let accounts = (0..20).map(|seed| make_account(seed, 10)).collect::<Vec<_>>();
+        let tree = StateTree::from_accounts(&PlainSha256Hasher, &accounts).await;
+        let root = tree.root().await;
+
+        for seed in 20..40 {
+            let absent = make_account(seed, 10);
+            let proof = tree.prove(&absent.address).await;
+            assert!(verify_account(&PlainSha256Hasher, &root, &absent.address, None, &proof).await);
+            assert!(!verify_account(&PlainSha256Hasher, &root, &absent.address, Some(&absent), &proof).await);
+        }
+
+        // the proof of a present account can not be reused to claim its absence
+        let present = &accounts[3];
+        let mut proof = tree.prove(&present.address).await;
+        assert!(!verify_account(&PlainSha256Hasher, &root, &present.address, None, &proof).await);
+        proof.leaf = None;
+        assert!(!verify_account(&PlainSha256Hasher, &root, &present.address, None, &proof).await);
+    }
+
+    #[tokio::test]
+    async fn test_root_updates() {
+        let accounts = (0..10).map(|seed| make_account(seed, 10)).collect::<Vec<_>>();
+        let mut tree = StateTree::from_accounts(&PlainSha256Hasher, &accounts).await;
+        let root = tree.root().await;
+
+        let mut reversed = accounts.clone();
+        reversed.reverse();
+        assert_eq!(StateTree::from_accounts(&PlainSha256Hasher, &reversed).await.root().await, root);
+
+        let mut updated = accounts[5].clone();
+        updated.nonce = 1;
+        tree.set(&updated).await;
+        assert_ne!(tree.root().await, root);
+        tree.set(&accounts[5]).await;
+        assert_eq!(tree.root().await, root);
+
+        let extra = make_account(10, 10);
+        tree.set(&extra).await;
+        assert_ne!(tree.root().await, root);
+        tree.remove(&extra.address).await;
+        assert_eq!(tree.root().await, root);
+    }
+
+    #[tokio::test]
+    async fn test_single_account() {
+        let account = make_account(1, 100);
+        let tree = StateTree::from_accounts(&PlainSha256Hasher, std::slice::from_ref(&account)).await;
+        let root = tree.root().await;
+
+        let proof = tree.prove(&account.address).await;
+        assert!(proof.siblings.is_empty());
+        assert!(verify_account(&PlainSha256Hasher, &root, &account.address, Some(&account), &proof).await);
+
+        let absent = make_account(2, 100);
+        let proof = tree.prove(&absent.address).await;
+        assert_eq!(proof.leaf.as_ref().unwrap().key, state_key(&PlainSha256Hasher, &account.address).await);
+        assert!(verify_account(&PlainSha256Hasher, &root, &absent.address, None, &proof).await);
+    }
+
+    fn make_denom(base: &str, decimals: u8) -> DenomMetadata {
+        DenomMetadata {
+            base: base.to_string(),
+            display: base.to_uppercase(),
+            decimals,
+            symbol: base.to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entries_and_denoms() {
+        let accounts = (0..10).map(|seed| make_account(seed, 10)).collect::<Vec<_>>();
+        let mut tree = StateTree::from_accounts(&PlainSha256Hasher, &accounts).await;
+        let accounts_root = tree.root().await;
+
+        tree.set_entry("token/denoms/factory/alice/coin", b"supply").await;
+        let entry_root = tree.root().await;
+        assert_ne!(entry_root, accounts_root);
+
+        let proof = tree.prove_entry("token/denoms/factory/alice/coin").await;
+        assert!(verify_entry(&PlainSha256Hasher, &entry_root, "token/denoms/factory/alice/coin", Some(b"supply"), &proof).await);
+        assert!(!verify_entry(&PlainSha256Hasher, &entry_root, "token/denoms/factory/alice/coin", Some(b"forged"), &proof).await);
+        assert!(!verify_entry(&PlainSha256Hasher, &entry_root, "token/denoms/factory/alice/coin", None, &proof).await);
+
+        let proof = tree.prove_entry("bank/allowances/absent").await;
+        assert!(verify_entry(&PlainSha256Hasher, &entry_root, "bank/allowances/absent", None, &proof).await);
+
+        let denom = make_denom("ucoin", 6);
+        tree.set_denom(&denom).await;
+        let root = tree.root().await;
+        assert_ne!(root, entry_root);
+
+        let proof = tree.prove_denom("ucoin").await;
+        assert!(verify_denom(&PlainSha256Hasher, &root, "ucoin", Some(&denom), &proof).await);
+        assert!(!verify_denom(&PlainSha256Hasher, &root, "ucoin", Some(&make_denom("ucoin", 8)), &proof).await);
+        assert!(!verify_denom(&PlainSha256Hasher, &root, "other", Some(&denom), &proof).await);
+        let proof = tree.prove_denom("other").await;
+        assert!(verify_denom(&PlainSha256Hasher, &root, "other", None, &proof).await);
+
+        // accounts are still provable next to the other namespaces
+        let proof = tree.prove(&accounts[4].address).await;
+        assert!(verify_account(&PlainSha256Hasher, &root, &accounts[4].address, Some(&accounts[4]), &proof).await);
+
+        tree.remove_entry("token/denoms/factory/alice/coin").await;
+        tree.set_entry("token/denoms/factory/alice/coin", b"minted").await;
+        assert_ne!(tree.root().await, root);
+        tree.set_entry("token/denoms/factory/alice/coin", b"supply").await;
+        assert_eq!(tree.root().await, root);
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_do_not_collide() {
+        let mut tree = StateTree::new(&PlainSha256Hasher);
+        tree.set_entry("LOKI", b"").await;
+        let root = tree.root().await;
+
+        // the same name in another namespace is a different key
+        let proof = tree.prove_denom("LOKI").await;
+        assert!(verify_denom(&PlainSha256Hasher, &root, "LOKI", None, &proof).await);
+        assert!(!verify_denom(&PlainSha256Hasher, &root, "LOKI", Some(&make_denom("LOKI", 0)), &proof).await);
+
+        tree.set_denom(&make_denom("LOKI", 0)).await;
+        let proof = tree.prove_entry("LOKI").await;
+        assert!(verify_entry(&PlainSha256Hasher, &tree.root().await, "LOKI", Some(b""), &proof).await);
+    }
+}
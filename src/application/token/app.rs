@@ -0,0 +1,518 @@
+use crate::application::common::app::{decode_payload, App};
+use crate::application::common::config::NodeConfig;
+use crate::application::common::context::{ExecutionContext, Stores};
+use crate::application::common::exceptions::ApplicationError;
+use crate::domain::models::address::Address;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::denom::DenomMetadata;
+use crate::domain::models::factory_denom::FactoryDenom;
+use crate::domain::models::receipt::Event;
+use crate::domain::models::token::{decimal, decimal_token, Token};
+use crate::domain::models::transaction::Transaction;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Longest subdenom, keeps the `factory/<creator>/<subdenom>` unit of a single key creator within the unit limit
+pub const MAX_SUBDENOM_LEN: usize = 32;
+
+/// Payload of `token`/`create_denom`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateDenomPayload {
+    /// ex: `points` for `factory/<creator>/points`
+    pub subdenom: String,
+    #[serde(with = "decimal")]
+    pub max_supply: u128
+}
+
+/// Payload of `token`/`mint`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintPayload {
+    #[serde(with = "decimal_token")]
+    pub amount: Token,
+    /// the sender if not set
+    #[serde(default)]
+    pub receiver: Option<Address>
+}
+
+/// Payload of `token`/`burn`, burns from the sender
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BurnPayload {
+    #[serde(with = "decimal_token")]
+    pub amount: Token
+}
+
+/// Payload of `token`/`change_admin`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeAdminPayload {
+    pub denom: String,
+    /// `None` renounces the admin for good
+    pub new_admin: Option<Address>
+}
+
+/// Payload of `token`/`set_metadata`, the base unit is `denom`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetMetadataPayload {
+    pub denom: String,
+    /// the base unit or a unit in the namespace of the creator
+    pub display: String,
+    /// can not change once the denom has a supply
+    pub decimals: u8,
+    pub symbol: String
+}
+
+/// Payload of the `denom` query
+#[derive(Clone, Debug, Deserialize)]
+pub struct DenomQuery {
+    pub denom: String
+}
+
+/// Answer of the `denom` query
+#[derive(Clone, Debug, Serialize)]
+pub struct DenomInfo {
+    #[serde(flatten)]
+    pub denom: FactoryDenom,
+    pub metadata: Option<DenomMetadata>
+}
+
+/// Issues denoms namespaced to their creator
+///
+/// Operations:
+/// - `create_denom`: registers `factory/<sender>/<subdenom>` with a supply cap, the sender becomes its admin
+/// - `mint`: the admin mints up to the supply cap
+/// - `burn`: any holder burns its own tokens
+/// - `change_admin`: the admin hands over or renounces the admin
+/// - `set_metadata`: the admin sets the display unit, decimals and symbol
+///
+/// Queries:
+/// - `denom`: supply, cap, admin and metadata of a denom
+pub struct TokenApp<'a> {
+    pub config: &'a NodeConfig
+}
+
+fn invalid(key: &str, reason: &str) -> ApplicationError {
+    ApplicationError::InvalidData([(key.to_string(), reason.to_string())].into())
+}
+
+fn state_key(denom: &str) -> String {
+    format!("token/denoms/{}", denom)
+}
+
+fn is_factory_denom(denom: &str) -> bool {
+    denom.starts_with("factory/")
+}
+
+impl TokenApp<'_> {
+    async fn factory_denom(&self, ctx: &mut ExecutionContext<'_>, denom: &str) -> Result<FactoryDenom, ApplicationError> {
+        ctx.state(&state_key(denom)).await?
+            .ok_or_else(|| ApplicationError::NotFound(format!("denom {}", denom)))
+    }
+
+    /// Loads the denom the sender is the admin of
+    async fn admin_denom(&self, ctx: &mut ExecutionContext<'_>, denom: &str, sender: &Address) -> Result<FactoryDenom, ApplicationError> {
+        let factory_denom = self.factory_denom(ctx, denom).await?;
+        if factory_denom.admin.as_ref() != Some(sender) {
+            return Err(invalid("body.sender", "sender is not the admin"));
+        }
+        Ok(factory_denom)
+    }
+
+    async fn create_denom(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<Event, ApplicationError> {
+        let payload: CreateDenomPayload = decode_payload(&transaction.data.payload)?;
+        let factory_denom = FactoryDenom::new(&transaction.sender, &payload.subdenom, payload.max_supply);
+        let key = state_key(&factory_denom.denom);
+        if ctx.state::<FactoryDenom>(&key).await?.is_some() {
+            return Err(invalid("body.data.payload.subdenom", "denom already exists"));
+        }
+
+        ctx.set_denom(DenomMetadata {
+            base: factory_denom.denom.clone(),
+            display: factory_denom.denom.clone(),
+            decimals: 0,
+            symbol: payload.subdenom.clone()
+        }).await?;
+        ctx.set_state(&key, &factory_denom)?;
+        Ok(Event::new("token", "create_denom")
+            .with("denom", &factory_denom.denom)
+            .with("creator", &transaction.sender)
+            .with("max_supply", payload.max_supply))
+    }
+
+    async fn mint(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<Event, ApplicationError> {
+        let payload: MintPayload = decode_payload(&transaction.data.payload)?;
+        let mut factory_denom = self.admin_denom(ctx, &payload.amount.denom, &transaction.sender).await?;
+        factory_denom.mint(payload.amount.value)?;
+        ctx.set_state(&state_key(&factory_denom.denom), &factory_denom)?;
+
+        let receiver = payload.receiver.unwrap_or_else(|| transaction.sender.clone());
+        ctx.deposit(&receiver, &payload.amount).await?;
+        Ok(Event::new("token", "mint")
+            .with("denom", &factory_denom.denom)
+            .with("receiver", &receiver)
+            .with("amount", payload.amount.value))
+    }
+
+    async fn burn(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<Event, ApplicationError> {
+        let payload: BurnPayload = decode_payload(&transaction.data.payload)?;
+        let mut factory_denom = self.factory_denom(ctx, &payload.amount.denom).await?;
+        ctx.withdraw(&transaction.sender, &payload.amount).await?;
+        factory_denom.burn(payload.amount.value)?;
+        ctx.set_state(&state_key(&factory_denom.denom), &factory_denom)?;
+        Ok(Event::new("token", "burn")
+            .with("denom", &factory_denom.denom)
+            .with("sender", &transaction.sender)
+            .with("amount", payload.amount.value))
+    }
+
+    async fn change_admin(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<Event, ApplicationError> {
+        let payload: ChangeAdminPayload = decode_payload(&transaction.data.payload)?;
+        let mut factory_denom = self.admin_denom(ctx, &payload.denom, &transaction.sender).await?;
+        factory_denom.admin = payload.new_admin;
+        ctx.set_state(&state_key(&factory_denom.denom), &factory_denom)?;
+        let admin = factory_denom.admin.as_ref().map(Address::to_string).unwrap_or_default();
+        Ok(Event::new("token", "change_admin")
+            .with("denom", &factory_denom.denom)
+            .with("admin", admin))
+    }
+
+    async fn set_metadata(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<Event, ApplicationError> {
+        let payload: SetMetadataPayload = decode_payload(&transaction.data.payload)?;
+        let factory_denom = self.admin_denom(ctx, &payload.denom, &transaction.sender).await?;
+        let metadata = ctx.denom(&payload.denom).await?
+            .ok_or_else(|| ApplicationError::NotFound(format!("denom {}", payload.denom)))?;
+
+        // a display unit outside the namespace could pass off as another asset
+        if payload.display != factory_denom.denom && !payload.display.starts_with(&FactoryDenom::namespace(&factory_denom.creator)) {
+            return Err(invalid("body.data.payload.display", "display unit must be in the namespace of the creator"));
+        }
+        if payload.decimals != metadata.decimals && factory_denom.supply > 0 {
+            return Err(invalid("body.data.payload.decimals", "decimals can not change once minted"));
+        }
+
+        ctx.set_denom(DenomMetadata {
+            base: factory_denom.denom.clone(),
+            display: payload.display,
+            decimals: payload.decimals,
+            symbol: payload.symbol
+        }).await?;
+        Ok(Event::new("token", "set_metadata").with("denom", &factory_denom.denom))
+    }
+
+    fn check_network(&self, address: &Address, key: &str) -> Result<(), ApplicationError> {
+        if address.network != self.config.hrp {
+            return Err(invalid(key, "network is not valid"));
+        }
+        Ok(())
+    }
+
+    fn check_amount(&self, amount: &Token) -> Result<(), ApplicationError> {
+        if !is_factory_denom(&amount.denom) {
+            return Err(invalid("body.data.payload.amount", "denom is not issued by the token app"));
+        }
+        if amount.is_zero() {
+            return Err(invalid("body.data.payload.amount", "amount must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl App for TokenApp<'_> {
+    fn name(&self) -> &str {
+        "token"
+    }
+
+    fn operations(&self) -> &[&str] {
+        &["create_denom", "mint", "burn", "change_admin", "set_metadata"]
+    }
+
+    fn validate(&self, data: &AppData) -> Result<(), ApplicationError> {
+        match data.operation.as_str() {
+            "create_denom" => {
+                let payload: CreateDenomPayload = decode_payload(&data.payload)?;
+                let is_valid = !payload.subdenom.is_empty()
+                    && payload.subdenom.len() <= MAX_SUBDENOM_LEN
+                    && payload.subdenom.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+                if !is_valid {
+                    return Err(invalid(
+                        "body.data.payload.subdenom",
+                        &format!("subdenom must be 1 to {} ascii letters, digits, `-` or `_`", MAX_SUBDENOM_LEN)
+                    ));
+                }
+                if payload.max_supply == 0 {
+                    return Err(invalid("body.data.payload.max_supply", "max supply must be greater than 0"));
+                }
+                Ok(())
+            }
+            "mint" => {
+                let payload: MintPayload = decode_payload(&data.payload)?;
+                self.check_amount(&payload.amount)?;
+                match &payload.receiver {
+                    Some(receiver) => self.check_network(receiver, "body.data.payload.receiver"),
+                    None => Ok(()),
+                }
+            }
+            "burn" => self.check_amount(&decode_payload::<BurnPayload>(&data.payload)?.amount),
+            "change_admin" => {
+                let payload: ChangeAdminPayload = decode_payload(&data.payload)?;
+                match &payload.new_admin {
+                    Some(admin) => self.check_network(admin, "body.data.payload.new_admin"),
+                    None => Ok(()),
+                }
+            }
+            "set_metadata" => {
+                decode_payload::<SetMetadataPayload>(&data.payload)?;
+                Ok(())
+            }
+            operation => Err(ApplicationError::NotFound(format!("token/{}", operation))),
+        }
+    }
+
+    /// Only `burn` takes tokens from the sender, `[Transaction::amount]` is never moved
+    fn sender_debit(&self, data: &AppData, _amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        match data.operation.as_str() {
+            "burn" => Ok(vec![("body.data.payload.amount", decode_payload::<BurnPayload>(&data.payload)?.amount)]),
+            _ => Ok(vec![]),
+        }
+    }
+
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let event = match transaction.data.operation.as_str() {
+            "create_denom" => self.create_denom(transaction, ctx).await?,
+            "mint" => self.mint(transaction, ctx).await?,
+            "burn" => self.burn(transaction, ctx).await?,
+            "change_admin" => self.change_admin(transaction, ctx).await?,
+            "set_metadata" => self.set_metadata(transaction, ctx).await?,
+            operation => return Err(ApplicationError::NotFound(format!("token/{}", operation))),
+        };
+        ctx.emit(event);
+        Ok(())
+    }
+
+    async fn query(&self, query: &str, payload: &Value, stores: Stores<'_>) -> Result<Value, ApplicationError> {
+        match query {
+            "denom" => {
+                let payload: DenomQuery = decode_payload(payload)?;
+                let bytes = stores.state.get(&state_key(&payload.denom)).await
+                    .ok_or_else(|| ApplicationError::NotFound(format!("denom {}", payload.denom)))?;
+                let info = DenomInfo {
+                    denom: serde_json::from_slice(&bytes).map_err(anyhow::Error::from)?,
+                    metadata: stores.denoms.get(&payload.denom).await
+                };
+                Ok(serde_json::to_value(info).map_err(anyhow::Error::from)?)
+            }
+            _ => Err(ApplicationError::NotFound(format!("query {}", query))),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::acc_storage::AccStorage;
+    use crate::application::common::context::tests::MockStores;
+    use crate::domain::exceptions::DomainError;
+    use crate::application::common::denom_registry::DenomRegistry;
+    use crate::application::common::gas::{GasMeter, GasSchedule};
+    use crate::domain::models::fee::Fee;
+    use crate::domain::models::hash::Hash;
+    use crate::domain::models::signature::{Signature, VerifyKey};
+
+    fn address(seed: u8) -> Address {
+        Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) }
+    }
+
+    fn points() -> String {
+        format!("factory/{}/points", address(1))
+    }
+
+    fn transaction(sender: u8, operation: &str, payload: Value) -> Transaction {
+        Transaction::new(
+            Hash([0; 32]),
+            address(sender),
+            AppData { app: "token".to_string(), operation: operation.to_string(), payload },
+            Token::new(1, "LOKI"),
+            0,
+            Fee::new(100_000, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0; 64])
+        )
+    }
+
+    /// Executes a single operation and commits it if it succeeded
+    async fn run(app: &TokenApp<'_>, mocks: &MockStores, sender: u8, operation: &str, payload: Value) -> Result<(), ApplicationError> {
+        let transaction = transaction(sender, operation, payload);
+        app.validate(&transaction.data)?;
+        let mut ctx = ExecutionContext::new(mocks.stores(), GasMeter::new(100_000, GasSchedule::default()));
+        app.execute(&transaction, &mut ctx).await?;
+        ctx.commit().await;
+        Ok(())
+    }
+
+    async fn create_points(app: &TokenApp<'_>, mocks: &MockStores) {
+        run(app, mocks, 1, "create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 1000 })).await.unwrap();
+    }
+
+    fn mint(amount: u128, receiver: Option<Address>) -> Value {
+        serde_json::to_value(MintPayload { amount: Token::new(amount, &points()), receiver }).unwrap()
+    }
+
+    async fn balance(mocks: &MockStores, seed: u8) -> u128 {
+        mocks.accounts.get(&address(seed)).await.map_or(0, |acc| acc.balances.get(&points()).value)
+    }
+
+    async fn supply(app: &TokenApp<'_>, mocks: &MockStores) -> u128 {
+        let info = app.query("denom", &serde_json::json!({ "denom": points() }), mocks.stores()).await.unwrap();
+        info["supply"].as_str().unwrap().parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_mint() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+
+        create_points(&app, &mocks).await;
+        let metadata = mocks.denoms.get(&points()).await.unwrap();
+        assert_eq!((metadata.display.as_str(), metadata.decimals), (points().as_str(), 0));
+        // the namespace keeps the same subdenom of another creator apart
+        run(&app, &mocks, 2, "create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 10 })).await.unwrap();
+        let result = run(&app, &mocks, 1, "create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 10 })).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors["body.data.payload.subdenom"] == "denom already exists"));
+
+        run(&app, &mocks, 1, "mint", mint(600, Some(address(3)))).await.unwrap();
+        run(&app, &mocks, 1, "mint", mint(400, None)).await.unwrap();
+        assert_eq!((balance(&mocks, 3).await, balance(&mocks, 1).await), (600, 400));
+        assert_eq!(supply(&app, &mocks).await, 1000);
+
+        let result = run(&app, &mocks, 1, "mint", mint(1, None)).await;
+        assert!(matches!(result, Err(ApplicationError::Domain(DomainError::SupplyCapExceeded(_)))));
+        let result = run(&app, &mocks, 3, "mint", mint(1, None)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.sender")));
+
+        let info = app.query("denom", &serde_json::json!({ "denom": points() }), mocks.stores()).await.unwrap();
+        assert_eq!(info["max_supply"], "1000");
+        assert_eq!(info["admin"], address(1).to_string());
+        assert_eq!(info["metadata"]["symbol"], "points");
+    }
+
+    #[tokio::test]
+    async fn test_burn() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+        create_points(&app, &mocks).await;
+        run(&app, &mocks, 1, "mint", mint(100, Some(address(3)))).await.unwrap();
+
+        let burn = |amount: u128| serde_json::to_value(BurnPayload { amount: Token::new(amount, &points()) }).unwrap();
+        run(&app, &mocks, 3, "burn", burn(30)).await.unwrap();
+        assert_eq!(balance(&mocks, 3).await, 70);
+        assert_eq!(supply(&app, &mocks).await, 70);
+
+        assert!(run(&app, &mocks, 3, "burn", burn(71)).await.is_err());
+        assert!(run(&app, &mocks, 1, "burn", burn(1)).await.is_err());
+        assert_eq!(supply(&app, &mocks).await, 70);
+
+        // the burned supply can be minted again
+        run(&app, &mocks, 1, "mint", mint(930, None)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_change_admin() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+        create_points(&app, &mocks).await;
+
+        run(&app, &mocks, 1, "change_admin", serde_json::json!({ "denom": points(), "new_admin": address(2) })).await.unwrap();
+        assert!(run(&app, &mocks, 1, "mint", mint(1, None)).await.is_err());
+        run(&app, &mocks, 2, "mint", mint(1, None)).await.unwrap();
+
+        run(&app, &mocks, 2, "change_admin", serde_json::json!({ "denom": points(), "new_admin": null })).await.unwrap();
+        assert!(run(&app, &mocks, 2, "mint", mint(1, None)).await.is_err());
+        assert!(run(&app, &mocks, 2, "change_admin", serde_json::json!({ "denom": points(), "new_admin": address(2) })).await.is_err());
+
+        let foreign = Address { network: "otherchain".to_string(), vk: VerifyKey::Ed25519([2; 32]) };
+        let data = transaction(1, "change_admin", serde_json::json!({ "denom": points(), "new_admin": foreign })).data;
+        assert!(app.validate(&data).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_metadata() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+        create_points(&app, &mocks).await;
+
+        let metadata = |display: &str, decimals: u8| serde_json::json!({
+            "denom": points(), "display": display, "decimals": decimals, "symbol": "PTS"
+        });
+        let display = format!("factory/{}/PTS", address(1));
+        run(&app, &mocks, 1, "set_metadata", metadata(&display, 2)).await.unwrap();
+        assert_eq!(mocks.denoms.find_unit(&display).await.unwrap().base, points());
+        assert_eq!(mocks.denoms.parse_amount(&format!("1.5 {}", display)).await.unwrap(), Token::new(150, &points()));
+
+        // a unit outside the namespace of the creator could impersonate another asset
+        let result = run(&app, &mocks, 1, "set_metadata", metadata("USDT", 2)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.display")));
+        assert!(run(&app, &mocks, 2, "set_metadata", metadata(&display, 2)).await.is_err());
+
+        run(&app, &mocks, 1, "mint", mint(1, None)).await.unwrap();
+        let result = run(&app, &mocks, 1, "set_metadata", metadata(&display, 6)).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidData(errors)) if errors.contains_key("body.data.payload.decimals")));
+        run(&app, &mocks, 1, "set_metadata", metadata(&display, 2)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_amounts_above_u64() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+
+        let create = serde_json::to_value(CreateDenomPayload { subdenom: "points".to_string(), max_supply: u128::MAX }).unwrap();
+        assert_eq!(create["max_supply"], u128::MAX.to_string());
+        run(&app, &mocks, 1, "create_denom", create).await.unwrap();
+
+        let amount = u64::MAX as u128 * 4;
+        run(&app, &mocks, 1, "mint", mint(amount, Some(address(3)))).await.unwrap();
+        assert_eq!(balance(&mocks, 3).await, amount);
+        assert_eq!(supply(&app, &mocks).await, amount);
+
+        // an amount sent as a number above u64::MAX can not be read
+        let payload = serde_json::json!({ "amount": { "denom": points(), "value": amount as f64 } });
+        assert!(matches!(app.validate(&transaction(1, "mint", payload).data), Err(ApplicationError::InvalidData(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failed_operation_reverts() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let mocks = MockStores::new();
+
+        let transaction = transaction(1, "create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 1000 }));
+        let mut ctx = ExecutionContext::new(mocks.stores(), GasMeter::new(100_000, GasSchedule::default()));
+        app.execute(&transaction, &mut ctx).await.unwrap();
+        drop(ctx);
+        assert!(mocks.denoms.get(&points()).await.is_none());
+        assert!(app.query("denom", &serde_json::json!({ "denom": points() }), mocks.stores()).await.is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = NodeConfig::default();
+        let app = TokenApp { config: &config };
+        let validate = |operation: &str, payload: Value| app.validate(&transaction(1, operation, payload).data);
+
+        assert!(validate("create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 1 })).is_ok());
+        assert!(validate("create_denom", serde_json::json!({ "subdenom": "", "max_supply": 1 })).is_err());
+        assert!(validate("create_denom", serde_json::json!({ "subdenom": "a/b", "max_supply": 1 })).is_err());
+        assert!(validate("create_denom", serde_json::json!({ "subdenom": "p".repeat(MAX_SUBDENOM_LEN + 1), "max_supply": 1 })).is_err());
+        assert!(validate("create_denom", serde_json::json!({ "subdenom": "points", "max_supply": 0 })).is_err());
+
+        assert!(validate("mint", mint(1, None)).is_ok());
+        assert!(validate("mint", mint(0, None)).is_err());
+        assert!(validate("mint", serde_json::json!({ "amount": { "denom": "LOKI", "value": 1 } })).is_err());
+        assert!(validate("burn", serde_json::json!({ "amount": { "denom": "LOKI", "value": 1 } })).is_err());
+    }
+}
@@ -1,29 +1,24 @@
 use crate::application::common::acc_storage::AccStorage;
 use crate::application::common::app_router::AppRouter;
+use crate::application::common::block_storage::BlockStorage;
+use crate::application::common::clock::Clock;
+use crate::application::common::config::NodeConfig;
+use crate::application::common::denom_registry::DenomRegistry;
 use crate::application::common::exceptions::ApplicationError;
 use crate::application::common::hasher::Hasher;
 use crate::application::common::interactor::Interactor;
 use crate::application::common::mempool::MemPool;
 use crate::application::common::signer::Signer;
-use crate::domain::models::address::Address;
-use crate::domain::models::app_data::AppData;
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::account::Balances;
 use crate::domain::models::hash::Hash;
 use crate::domain::models::signature::Signature;
-use crate::domain::models::token::Token;
-use crate::domain::models::transaction::{Transaction, TransactionWithState, TxState};
+use crate::domain::models::transaction::{Transaction, TransactionWithState, TxBody, TxState};
+use crate::domain::services::encoding::encode;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct TxBody {
-    pub sender: Address,
-    pub data: AppData,
-    pub amount: Token,
-    pub gas: u64,
-    pub nonce: u64
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct CreateTransactionRequest {
     pub body: TxBody,
     pub hash: Hash,
@@ -32,7 +27,9 @@ pub struct CreateTransactionRequest {
 
 #[derive(Debug, Serialize)]
 pub struct CreateTransactionResult {
-    pub hash: Hash
+    pub hash: Hash,
+    /// `Queued` while a transaction with a lower nonce is missing
+    pub state: TxState
 }
 
 pub struct CreateTransaction<'a> {
@@ -40,7 +37,11 @@ pub struct CreateTransaction<'a> {
     pub mem_pool: &'a dyn MemPool,
     pub app_router: &'a dyn AppRouter,
     pub signer: &'a dyn Signer,
-    pub acc_storage: &'a dyn AccStorage
+    pub acc_storage: &'a dyn AccStorage,
+    pub block_storage: &'a dyn BlockStorage,
+    pub clock: &'a dyn Clock,
+    pub denom_registry: &'a dyn DenomRegistry,
+    pub config: &'a NodeConfig
 }
 
 #[async_trait]
@@ -49,11 +50,7 @@ impl Interactor<CreateTransactionRequest, CreateTransactionResult> for CreateTra
         &self,
         data: CreateTransactionRequest
     ) -> Result<CreateTransactionResult, ApplicationError> {
-        let hash = {
-            let mut bytes = vec![];
-            bytes.extend_from_slice(&serde_json::to_vec(&data.body).unwrap());
-            self.hasher.hash(&bytes).await
-        };
+        let hash = self.hasher.hash(&encode(&data.body)).await;
 
         if hash != data.hash {
             return Err(ApplicationError::InvalidData(
@@ -61,18 +58,46 @@ impl Interactor<CreateTransactionRequest, CreateTransactionResult> for CreateTra
             ));
         }
 
-        if !self.signer.verify(&data.hash.0, &data.signature, &data.body.sender.vk).await {
+        if data.body.chain_id != self.config.chain_id {
             return Err(ApplicationError::InvalidData(
-                [("signature".to_string(), "signature is not valid".to_string())].into()
+                [("body.chain_id".to_string(), "chain id is not valid".to_string())].into()
             ));
         }
 
+        if data.body.sender.network != self.config.hrp {
+            return Err(ApplicationError::InvalidData(
+                [("body.sender".to_string(), "network is not valid".to_string())].into()
+            ));
+        }
+
+        // a recoverable signature names its signer, which only has to be the sender
+        let recovered = match self.config.recover_sender {
+            true => self.signer.recover(&data.hash.0, &data.signature).await,
+            false => None,
+        };
+        match recovered {
+            Some(recovered) if recovered != data.body.sender.vk => {
+                return Err(ApplicationError::InvalidData(
+                    [("signature".to_string(), "signer is not the sender".to_string())].into()
+                ));
+            }
+            Some(_) => {}
+            None if !self.signer.verify(&data.hash.0, &data.signature, &data.body.sender.vk).await => {
+                return Err(ApplicationError::InvalidData(
+                    [("signature".to_string(), "signature is not valid".to_string())].into()
+                ));
+            }
+            None => {}
+        }
+
         if !self.app_router.is_exist(&data.body.data.app, &data.body.data.operation).await {
             return Err(ApplicationError::InvalidData(
                 [("body.data".to_string(), "is not valid".to_string())].into()
             ));
         }
 
+        self.app_router.validate(&data.body.data).await?;
+
         
         if self.mem_pool.get(&data.hash).await.is_some() {
             return Err(ApplicationError::InvalidData(
@@ -80,56 +105,165 @@ impl Interactor<CreateTransactionRequest, CreateTransactionResult> for CreateTra
             ));
         }
 
-        if data.body.gas == 0 {
+        if data.body.fee.gas_limit == 0 {
+            return Err(ApplicationError::InvalidData(
+                [("body.fee.gas_limit".to_string(), "gas must be greater than 0".to_string())].into()
+            ));
+        }
+
+        match self.config.min_gas_price(&data.body.fee.gas_price.denom) {
+            None => {
+                return Err(ApplicationError::InvalidData(
+                    [("body.fee.gas_price".to_string(), "denom is not accepted for fees".to_string())].into()
+                ));
+            }
+            Some(min_gas_price) if data.body.fee.gas_price < min_gas_price => {
+                return Err(ApplicationError::InvalidData(
+                    [("body.fee.gas_price".to_string(), "gas price is below the minimum".to_string())].into()
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let validity = data.body.validity();
+        if !validity.is_consistent() {
             return Err(ApplicationError::InvalidData(
-                [("body.gas".to_string(), "gas must be greater than 0".to_string())].into()
+                [("body.valid_until".to_string(), "validity window is empty".to_string())].into()
             ));
         }
 
-        if data.body.amount.value == 0 {
+        // the transaction can get at most into the next block
+        let height = self.block_storage.last().await.map_or(0, |header| header.height + 1);
+        let timestamp = self.clock.now();
+        if validity.is_expired(height, timestamp) {
+            return Err(ApplicationError::InvalidData(
+                [("body.valid_until".to_string(), "transaction is expired".to_string())].into()
+            ));
+        }
+        if validity.is_pending(height, timestamp) {
             return Err(ApplicationError::InvalidData(
-                [("body.amount".to_string(), "amount must be greater than 0".to_string())].into()
+                [("body.valid_after".to_string(), "transaction is not valid yet".to_string())].into()
             ));
         }
 
-        if let Some(acc) = self.acc_storage.get(&data.body.sender).await {
-            if acc.balance.value < data.body.amount.value {
+        // the amount is only checked by the operations that take it from the sender
+        let debit = self.app_router.sender_debit(&data.body.data, &data.body.amount).await?;
+        for (key, token) in &debit {
+            if token.is_zero() {
                 return Err(ApplicationError::InvalidData(
-                    [("body.sender".to_string(), "you dont have coins".to_string())].into()
+                    [(key.to_string(), "amount must be greater than 0".to_string())].into()
                 ));
             }
-
-            if acc.balance.denom != data.body.amount.denom {
+            if self.denom_registry.get(&token.denom).await.is_none() {
                 return Err(ApplicationError::InvalidData(
-                    [("body.amount".to_string(), "denom is not valid".to_string())].into()
+                    [(key.to_string(), "denom is not registered".to_string())].into()
                 ));
             }
+        }
 
-        } else {
+        if self.denom_registry.get(&data.body.fee.gas_price.denom).await.is_none() {
+            return Err(ApplicationError::InvalidData(
+                [("body.fee.gas_price".to_string(), "denom is not registered".to_string())].into()
+            ));
+        }
+
+        let Ok(max_fee) = data.body.fee.max_fee() else {
+            return Err(ApplicationError::InvalidData(
+                [("body.fee".to_string(), "fee is too large".to_string())].into()
+            ));
+        };
+
+        // the debit and the whole gas limit must be covered, possibly in different denoms,
+        // next to what the pending and queued transactions of the sender may spend
+        let in_pool = self.mem_pool.by_sender(&data.body.sender).await;
+        let mut required = debit.into_iter().map(|(_, token)| token).chain([max_fee]).collect::<Vec<_>>();
+        for tx in &in_pool {
+            let debit = self.app_router.sender_debit(&tx.transaction.data, &tx.transaction.amount).await?;
+            required.extend(debit.into_iter().map(|(_, token)| token));
+            required.extend(tx.transaction.fee.max_fee().ok());
+        }
+        let required: Result<Balances, DomainError> = required.into_iter().collect();
+        let account = self.acc_storage.get(&data.body.sender).await;
+        let balances = account.as_ref().map(|acc| acc.balances.clone()).unwrap_or_default();
+        let covered = match required {
+            Ok(required) => required.iter().all(|token| balances.get(&token.denom) >= token),
+            Err(_) => false,
+        };
+        if !covered {
             return Err(ApplicationError::InvalidData(
                 [("body.sender".to_string(), "you dont have coins".to_string())].into()
             ));
         }
 
+        let account_nonce = account.map_or(0, |acc| acc.nonce);
+        let state = self.nonce_state(&in_pool, account_nonce, data.body.nonce)?;
+
         let transaction = Transaction::new(
             data.hash,
             data.body.sender,
             data.body.data,
             data.body.amount,
-            data.body.gas,
+            timestamp,
+            data.body.fee,
             data.body.nonce,
             data.signature
-        );
+        ).with_validity(validity);
 
-        let state = TxState::PendingConfirmation;
-
-        let transaction_with_state = TransactionWithState::new(&transaction,state);
+        let transaction_with_state = TransactionWithState::new(&transaction, state.clone());
 
         self.mem_pool.add(transaction_with_state).await;
 
-        Ok(CreateTransactionResult { hash })
+        if state == TxState::PendingConfirmation {
+            self.promote_queued(&transaction).await;
+        }
+
+        Ok(CreateTransactionResult { hash, state })
     }
 }
 
+impl CreateTransaction<'_> {
+    /// `PendingConfirmation` for the nonce following the account nonce and the pending transactions
+    /// of the sender in `in_pool`, `Queued` for a later one. Used and stale nonces are rejected
+    fn nonce_state(&self, in_pool: &[TransactionWithState], account_nonce: u64, nonce: u64) -> Result<TxState, ApplicationError> {
+        let invalid = |reason: &str| ApplicationError::InvalidData(
+            [("body.nonce".to_string(), reason.to_string())].into()
+        );
+
+        if nonce < account_nonce {
+            return Err(invalid("nonce is too low"));
+        }
+
+        if in_pool.iter().any(|tx| tx.transaction.nonce == nonce) {
+            return Err(invalid("nonce is already used"));
+        }
+
+        let mut next_nonce = account_nonce;
+        while in_pool.iter().any(|tx| tx.state == TxState::PendingConfirmation && tx.transaction.nonce == next_nonce) {
+            next_nonce += 1;
+        }
+
+        if nonce == next_nonce {
+            Ok(TxState::PendingConfirmation)
+        } else if nonce - next_nonce <= self.config.max_nonce_gap {
+            Ok(TxState::Queued)
+        } else {
+            Err(invalid("nonce is too far ahead"))
+        }
+    }
 
+    /// Moves the queued transactions of the sender that follow `transaction` without a gap to pending
+    async fn promote_queued(&self, transaction: &Transaction) {
+        let mut queued = self.mem_pool.by_sender(&transaction.sender).await
+            .into_iter()
+            .filter(|tx| tx.state == TxState::Queued)
+            .collect::<Vec<_>>();
+        queued.sort_by_key(|tx| tx.transaction.nonce);
 
+        for (next_nonce, tx) in (transaction.nonce + 1..).zip(queued) {
+            if tx.transaction.nonce != next_nonce {
+                break;
+            }
+            self.mem_pool.add(TransactionWithState::new(&tx.transaction, TxState::PendingConfirmation)).await;
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::application::common::acc_storage::AccStorage;
+use crate::application::common::app_router::AppRouter;
+use crate::application::common::config::NodeConfig;
+use crate::application::common::context::{ExecutionContext, Stores};
+use crate::application::common::denom_registry::DenomRegistry;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::gas::GasMeter;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::state_storage::StateStorage;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::receipt::{Event, Receipt, RevertReason};
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::{Transaction, TxState};
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// Transaction applied at its position in a block
+#[derive(Debug)]
+pub struct ExecuteTransactionRequest {
+    pub transaction: Transaction,
+    /// height of the block the transaction is applied in
+    pub height: u64,
+    /// timestamp of the block the transaction is applied in
+    pub timestamp: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExecuteTransactionResult {
+    /// `Confirmed` if the operation succeeded, `Reverted` otherwise
+    pub state: TxState,
+    pub gas_used: u64,
+    /// fee paid by the sender after the refund
+    pub fee: Token,
+    /// set when the state is `Reverted`
+    pub error: Option<RevertReason>,
+    pub events: Vec<Event>
+}
+
+impl ExecuteTransactionResult {
+    /// Receipt of the transaction at `index` of the block at `block_height`
+    ///
+    /// `block_hash` is left zero until `[SaveReceipts]` stamps it once the block is sealed
+    ///
+    /// [SaveReceipts]: crate::application::block::receipts::SaveReceipts
+    pub fn into_receipt(self, tx_hash: Hash, block_height: u64, index: u32) -> Receipt {
+        Receipt {
+            tx_hash,
+            block_height,
+            block_hash: Hash([0; 32]),
+            index,
+            state: self.state,
+            gas_used: self.gas_used,
+            fee: self.fee,
+            error: self.error,
+            events: self.events
+        }
+    }
+}
+
+/// Executes a transaction taken from the mempool
+///
+/// The nonce must be the account nonce, it is bumped together with withdrawing
+/// the fee for the whole gas limit from the sender,
+/// then the payload and the signatures are charged and the operation is routed to its app.
+/// The unused gas is refunded, running out of gas uses up the whole limit.
+/// State changes of a failed operation are reverted, the fee is still paid.
+/// Fails without any change if the nonce is wrong or the sender can not pay the fee.
+/// The operation sees the height and timestamp of the block of the request, never the local time,
+/// so every node applying the block gets the same result
+pub struct ExecuteTransaction<'a> {
+    pub app_router: &'a dyn AppRouter,
+    pub acc_storage: &'a dyn AccStorage,
+    pub state_storage: &'a dyn StateStorage,
+    pub denom_registry: &'a dyn DenomRegistry,
+    pub config: &'a NodeConfig
+}
+
+impl ExecuteTransaction<'_> {
+    fn stores(&self) -> Stores<'_> {
+        Stores { accounts: self.acc_storage, state: self.state_storage, denoms: self.denom_registry }
+    }
+
+    async fn run(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        ctx.meter.charge_payload(&transaction.data.payload)?;
+        ctx.meter.charge_signature(&transaction.signature)?;
+        self.app_router.execute(transaction, ctx).await
+    }
+}
+
+#[async_trait]
+impl Interactor<ExecuteTransactionRequest, ExecuteTransactionResult> for ExecuteTransaction<'_> {
+    async fn execute(&self, data: ExecuteTransactionRequest) -> Result<ExecuteTransactionResult, ApplicationError> {
+        let transaction = data.transaction;
+        let mut sender = self.acc_storage.get(&transaction.sender).await
+            .ok_or_else(|| ApplicationError::NotFound(format!("account {}", transaction.sender)))?;
+        if transaction.nonce != sender.nonce {
+            return Err(ApplicationError::InvalidData(
+                [("nonce".to_string(), format!("expected nonce {}", sender.nonce))].into()
+            ));
+        }
+
+        // the nonce is used and the fee is paid even if the operation is reverted
+        let max_fee = transaction.fee.max_fee()?;
+        sender.balances.sub(&max_fee)?;
+        sender.nonce += 1;
+        self.acc_storage.set(transaction.sender.clone(), sender).await;
+
+        let meter = GasMeter::new(transaction.fee.gas_limit, self.config.gas_schedule.clone());
+        let mut ctx = ExecutionContext::new(self.stores(), meter).at_block(data.height, data.timestamp);
+        let result = self.run(&transaction, &mut ctx).await;
+        let gas_used = ctx.meter.used();
+        let (state, error, events) = match result {
+            Ok(()) => (TxState::Confirmed, None, ctx.commit().await),
+            Err(err) => (TxState::Reverted, Some(RevertReason { code: err.code(), message: err.to_string() }), vec![]),
+        };
+
+        let refund = transaction.fee.refund(gas_used)?;
+        self.acc_storage.deposit(&transaction.sender, &refund).await?;
+
+        Ok(ExecuteTransactionResult {
+            state,
+            gas_used,
+            fee: max_fee.checked_sub(&refund)?,
+            error,
+            events
+        })
+    }
+}
@@ -45,6 +45,7 @@ mod tests {
     use crate::application::common::tx_storage::tests::MockTxStorage;
     use crate::domain::models::address::Address;
     use crate::domain::models::app_data::AppData;
+    use crate::domain::models::fee::Fee;
     use crate::domain::models::hash::Hash;
     use crate::domain::models::signature::{Signature, VerifyKey};
     use crate::domain::models::token::Token;
@@ -55,7 +56,7 @@ mod tests {
             Hash([0; 32]),
             Address {
                 network: "lokichain".to_string(),
-                vk: VerifyKey([1; 32])
+                vk: VerifyKey::Ed25519([1; 32])
             },
             AppData {
                 app: "bank".to_string(),
@@ -63,9 +64,10 @@ mod tests {
                 payload: serde_json::Value::Null
             },
             Token { value: 100, denom: "LOKI".to_string() },
-            10,
             0,
-            Signature([0; 64])
+            Fee::new(10, Token::new(1, "LOKI")),
+            0,
+            Signature::Ed25519([0; 64])
         )
     }
 
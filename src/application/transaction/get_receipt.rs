@@ -0,0 +1,59 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::receipt_storage::ReceiptStorage;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::receipt::Receipt;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetReceiptRequest {
+    /// hash of the transaction
+    pub hash: Hash,
+}
+
+pub struct GetReceipt<'a> {
+    pub receipt_storage: &'a dyn ReceiptStorage,
+}
+
+#[async_trait]
+impl Interactor<GetReceiptRequest, Receipt> for GetReceipt<'_> {
+    async fn execute(&self, data: GetReceiptRequest) -> Result<Receipt, ApplicationError> {
+        self.receipt_storage.get(&data.hash).await
+            .ok_or_else(|| ApplicationError::NotFound("Receipt not found".to_string()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::receipt_storage::tests::MockReceiptStorage;
+    use crate::domain::models::receipt::RevertReason;
+    use crate::domain::models::token::Token;
+    use crate::domain::models::transaction::TxState;
+
+    #[tokio::test]
+    async fn test_get_receipt() {
+        let receipt_storage = MockReceiptStorage::new();
+        let receipt = Receipt {
+            tx_hash: Hash([1; 32]),
+            block_height: 7,
+            block_hash: Hash([2; 32]),
+            index: 0,
+            state: TxState::Reverted,
+            gas_used: 10,
+            fee: Token::new(10, "LOKI"),
+            error: Some(RevertReason { code: 3, message: "Out of gas: limit 10".to_string() }),
+            events: vec![]
+        };
+        receipt_storage.set(receipt.clone()).await;
+
+        let interactor = GetReceipt { receipt_storage: &receipt_storage };
+        let result = interactor.execute(GetReceiptRequest { hash: Hash([1; 32]) }).await.unwrap();
+        assert_eq!(result, receipt);
+
+        let result = interactor.execute(GetReceiptRequest { hash: Hash([9; 32]) }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound(msg)) if msg == "Receipt not found"));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod create;
+pub mod execute;
 pub mod get_by_hash;
+pub mod get_receipt;
 
 #[cfg(test)]
 mod tests;
\ No newline at end of file
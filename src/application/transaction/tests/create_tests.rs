@@ -1,22 +1,45 @@
  use crate::application::common::acc_storage::tests::MockAccStorage;
 use crate::application::common::app_router::tests::MockAppRouter;
-use crate::application::common::hasher::tests::MockHasher;
+use crate::application::common::app_router::AppRegistry;
+use crate::application::common::context::tests::MockStores;
+use crate::application::bank::app::BankApp;
+use crate::application::common::denom_registry::DenomRegistry;
+use crate::application::common::gas::GasSchedule;
+use crate::application::token::app::TokenApp;
+use crate::application::transaction::execute::{ExecuteTransaction, ExecuteTransactionRequest};
+use crate::domain::models::denom::DenomMetadata;
+use crate::application::common::block_storage::tests::MockBlockStorage;
+use crate::application::common::block_storage::BlockStorage;
+use crate::application::common::clock::ManualClock;
+use crate::application::common::denom_registry::tests::MockDenomRegistry;
+use crate::application::common::hasher::tests::{MockHasher, PlainSha256Hasher};
 use crate::application::common::mempool::tests::MockMemPool;
+use crate::application::common::mempool::MemPool;
 use crate::application::common::signer::tests::MockSigner;
 use crate::domain::models::address::Address;
 use crate::domain::models::app_data::AppData;
+use crate::domain::models::block::Block;
+use crate::domain::models::fee::Fee;
 use crate::domain::models::hash::Hash;
-use crate::domain::models::signature::{SignKey, Signature, VerifyKey};
+use crate::domain::models::signature::{MultisigKey, SignKey, Signature, VerifyKey};
 use crate::domain::models::token::Token;
+use crate::domain::models::validity::ValidityBound;
 use serde_json::Value;
  use crate::application::common::acc_storage::AccStorage;
+ use crate::application::common::config::NodeConfig;
  use crate::application::common::exceptions::ApplicationError;
  use crate::application::common::hasher::Hasher;
  use crate::application::common::interactor::Interactor;
  use crate::application::common::signer::Signer;
- use crate::application::transaction::create::{CreateTransaction, CreateTransactionRequest, TxBody};
+ use crate::application::transaction::create::{CreateTransaction, CreateTransactionRequest, CreateTransactionResult};
  use crate::domain::models::account::Account;
- use crate::domain::models::transaction::{Transaction, TransactionWithState, TxState};
+ use crate::domain::models::transaction::{Transaction, TransactionWithState, TxBody, TxState};
+ use crate::domain::services::encoding::encode;
+ use crate::infrastructure::signer::ed25519::Ed25519Signer;
+ use crate::infrastructure::signer::secp256k1::Secp256k1Signer;
+ use crate::infrastructure::signer::SchemeSigner;
+
+const NOW: u64 = 1_700_000_000;
 
  #[tokio::test]
 async fn test_create_transaction_ok() {
@@ -29,10 +52,14 @@ async fn test_create_transaction_ok() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -44,24 +71,26 @@ async fn test_create_transaction_ok() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -82,10 +111,14 @@ async fn test_create_transaction_invalid_hash() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -97,24 +130,26 @@ async fn test_create_transaction_invalid_hash() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
     let expected_hash = MockHasher.hash(&bytes).await;
     transaction.hash = Hash([1; 32]);
 
@@ -137,10 +172,14 @@ async fn test_create_transaction_invalid_signature() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -152,27 +191,29 @@ async fn test_create_transaction_invalid_signature() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
     transaction.hash = MockHasher.hash(&bytes).await;
     let correct_signature = signer.sign(&transaction.hash.0, &pk).await;
-    transaction.signature = Signature([1; 64]);
+    transaction.signature = Signature::Ed25519([1; 64]);
     let result = interactor.execute(transaction).await;
     assert!(result.is_err());
 
@@ -197,10 +238,14 @@ async fn test_is_exist_condition() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -212,7 +257,7 @@ async fn test_is_exist_condition() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() },
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into(),
         }
     ).await;
 
@@ -221,10 +266,13 @@ async fn test_is_exist_condition() {
 
     let transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData {
                 app: app_name.clone(),
                 operation: operation_name.clone(),
@@ -232,7 +280,7 @@ async fn test_is_exist_condition() {
             },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64]),
+        signature: Signature::Ed25519([0; 64]),
     };
 
 
@@ -254,10 +302,14 @@ async fn test_exist_transaction_mempool() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -269,25 +321,27 @@ async fn test_exist_transaction_mempool() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -297,7 +351,8 @@ async fn test_exist_transaction_mempool() {
         transaction.body.sender.clone(),
         transaction.body.data.clone(),
         transaction.body.amount.clone(),
-        transaction.body.gas,
+        0,
+        transaction.body.fee.clone(),
         transaction.body.nonce,
         transaction.signature.clone(),
     );
@@ -322,10 +377,14 @@ async fn test_gas() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -337,25 +396,27 @@ async fn test_gas() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 0,
+            fee: Fee::new(0, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -377,10 +438,14 @@ async fn test_amount_value() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -392,25 +457,27 @@ async fn test_amount_value() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 0, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -434,10 +501,14 @@ async fn test_insufficient_balance() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -449,25 +520,27 @@ async fn test_insufficient_balance() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 10, denom: "LOKI".to_string() }
+            balances: Token { value: 10, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 100, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -488,10 +561,14 @@ async fn test_invalid_denom() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -503,30 +580,52 @@ async fn test_invalid_denom() {
         Account {
             address: address.clone(),
             nonce: 0,
-            balance: Token { value: 100, denom: "LOKI".to_string() }
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
         }
     ).await;
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "Bitcoin".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    let result = interactor.execute(transaction.clone()).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = result {
+        assert_eq!(errors.get("body.amount").unwrap(), "denom is not registered");
+    } else {
+        panic!("unregistered denom must be rejected");
+    }
+
+    // registered, but the account does not hold it
+    transaction.body.amount = Token { value: 10, denom: "uusdt".to_string() };
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    let result = interactor.execute(transaction.clone()).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = result {
+        assert_eq!(errors.get("body.sender").unwrap(), "you dont have coins");
+    } else {
+        panic!("balance of the sent denom must be checked");
+    }
+
+    // held next to LOKI
+    acc_storage.deposit(&address, &Token { value: 10, denom: "uusdt".to_string() }).await.unwrap();
     let result = interactor.execute(transaction).await;
-    assert!(result.is_err());
+    assert!(result.is_ok());
 }
 
 
@@ -541,10 +640,14 @@ async fn test_account_not_found() {
         app_router: &MockAppRouter,
         signer: &signer,
         acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
     };
 
-    let pk = SignKey([1; 32]);
-    let vk = VerifyKey([1; 32]);
+    let pk = SignKey::Ed25519([1; 32]);
+    let vk = VerifyKey::Ed25519([1; 32]);
 
     let address = Address {
         network: "lokichain".to_string(),
@@ -555,19 +658,21 @@ async fn test_account_not_found() {
 
     let mut transaction = CreateTransactionRequest {
         body: TxBody {
+            chain_id: "lokichain-1".to_string(),
             sender: address.clone(),
             amount: Token { value: 10, denom: "LOKI".to_string() },
-            gas: 10,
+            fee: Fee::new(10, Token::new(1, "LOKI")),
             nonce: 0,
+            valid_after: None,
+            valid_until: None,
             data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
         },
         hash: Hash([0; 32]),
-        signature: Signature([0; 64])
+        signature: Signature::Ed25519([0; 64])
     };
 
 
-    let mut bytes = vec![];
-    bytes.extend_from_slice(&serde_json::to_vec(&transaction.body).unwrap());
+    let bytes = encode(&transaction.body);
 
     transaction.hash = MockHasher.hash(&bytes).await;
     transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
@@ -575,4 +680,830 @@ async fn test_account_not_found() {
 
     let result = interactor.execute(transaction).await;
     assert!(result.is_err());
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_create_transaction_ed25519() {
+    let acc_storage = MockAccStorage::new();
+    let signer = Ed25519Signer::new();
+    let hasher = MockHasher;
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let (pk, vk) = Ed25519Signer::keypair(&Ed25519Signer::generate_sign_key());
+    let forger_pk = Ed25519Signer::generate_sign_key();
+
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk
+    };
+
+    acc_storage.set(
+        address.clone(),
+        Account {
+            address: address.clone(),
+            nonce: 0,
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
+        }
+    ).await;
+
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Ed25519([0; 64])
+    };
+
+    let bytes = encode(&transaction.body);
+    transaction.hash = MockHasher.hash(&bytes).await;
+
+    let forged = CreateTransactionRequest {
+        body: transaction.body.clone(),
+        hash: transaction.hash.clone(),
+        signature: signer.sign(&transaction.hash.0, &forger_pk).await
+    };
+    let result = interactor.execute(forged).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = result {
+        assert_eq!(errors.get("signature").unwrap(), "signature is not valid");
+    } else {
+        panic!("forged signature must be rejected");
+    }
+
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    let result = interactor.execute(transaction).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_create_transaction_secp256k1() {
+    let acc_storage = MockAccStorage::new();
+    let signer = Secp256k1Signer::new();
+    let hasher = MockHasher;
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig { recover_sender: true, ..Default::default() },
+    };
+
+    let (pk, vk) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk
+    };
+
+    acc_storage.set(
+        address.clone(),
+        Account {
+            address: address.clone(),
+            nonce: 0,
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
+        }
+    ).await;
+
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Secp256k1([0; 65])
+    };
+
+    let bytes = encode(&transaction.body);
+    transaction.hash = MockHasher.hash(&bytes).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+
+    let result = interactor.execute(transaction).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_recovered_sender_mismatch() {
+    let acc_storage = MockAccStorage::new();
+    let signer = Secp256k1Signer::new();
+    let interactor = CreateTransaction {
+        hasher: &MockHasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig { recover_sender: true, ..Default::default() },
+    };
+
+    let (_, sender_vk) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+    let (other_pk, other_vk) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+    let address = Address { network: "lokichain".to_string(), vk: sender_vk };
+    acc_storage.deposit(&address, &Token::new(100, "LOKI")).await.unwrap();
+
+    let mut body = make_request(0, 10).await.body;
+    body.sender = address;
+    let hash = MockHasher.hash(&encode(&body)).await;
+    let signature = signer.sign(&hash.0, &other_pk).await;
+    assert_eq!(signer.recover(&hash.0, &signature).await, Some(other_vk));
+    let request = CreateTransactionRequest { body, hash, signature };
+
+    // the recovered signer is reported, not a bad signature
+    let (key, value) = create_error(interactor.execute(request.clone()).await).unwrap();
+    assert_eq!((key.as_str(), value.as_str()), ("signature", "signer is not the sender"));
+
+    let interactor = CreateTransaction {
+        config: &NodeConfig { recover_sender: false, ..Default::default() },
+        ..interactor
+    };
+    let (key, value) = create_error(interactor.execute(request).await).unwrap();
+    assert_eq!((key.as_str(), value.as_str()), ("signature", "signature is not valid"));
+}
+
+#[tokio::test]
+async fn test_create_transaction_multisig() {
+    let acc_storage = MockAccStorage::new();
+    let signer = SchemeSigner::default();
+    let hasher = MockHasher;
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig { recover_sender: true, ..Default::default() },
+    };
+
+    // treasury: three of five officers
+    let officers = (0..5).map(|_| Ed25519Signer::keypair(&Ed25519Signer::generate_sign_key())).collect::<Vec<_>>();
+    let key = MultisigKey::new(3, officers.iter().map(|(_, vk)| vk.clone()).collect()).unwrap();
+
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk: key.to_verify_key()
+    };
+
+    acc_storage.set(
+        address.clone(),
+        Account {
+            address: address.clone(),
+            nonce: 0,
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
+        }
+    ).await;
+
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Multisig(vec![])
+    };
+
+    let bytes = encode(&transaction.body);
+    transaction.hash = MockHasher.hash(&bytes).await;
+
+    let mut approvals = vec![];
+    for (pk, vk) in &officers {
+        approvals.push((key.index_of(vk).unwrap(), signer.sign(&transaction.hash.0, pk).await));
+    }
+    approvals.sort_by_key(|(index, _)| *index);
+
+    let two_approvals = CreateTransactionRequest {
+        body: transaction.body.clone(),
+        hash: transaction.hash.clone(),
+        signature: Signature::Multisig(approvals[..2].to_vec())
+    };
+    let result = interactor.execute(two_approvals).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = result {
+        assert_eq!(errors.get("signature").unwrap(), "signature is not valid");
+    } else {
+        panic!("two of five approvals must be rejected");
+    }
+
+    transaction.signature = Signature::Multisig(approvals[1..4].to_vec());
+    let result = interactor.execute(transaction).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_min_gas_price() {
+    let acc_storage = MockAccStorage::new();
+    let signer = MockSigner;
+    let hasher = MockHasher;
+    let config = NodeConfig {
+        min_gas_prices: vec![Token::new(2, "LOKI"), Token::new(50, "uusdt")],
+        ..Default::default()
+    };
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config,
+    };
+
+    let pk = SignKey::Ed25519([1; 32]);
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk: VerifyKey::Ed25519([1; 32])
+    };
+    acc_storage.deposit(&address, &Token::new(100, "LOKI")).await.unwrap();
+
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Ed25519([0; 64])
+    };
+
+    let cases = [
+        (Token::new(1, "LOKI"), Some("gas price is below the minimum")),
+        (Token::new(1, "USDT"), Some("denom is not accepted for fees")),
+        (Token::new(2, "LOKI"), None),
+    ];
+    for (gas_price, error) in cases {
+        transaction.body.fee.gas_price = gas_price;
+        transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+        transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+
+        match (interactor.execute(transaction.clone()).await, error) {
+            (Err(ApplicationError::InvalidData(errors)), Some(error)) => {
+                assert_eq!(errors.get("body.fee.gas_price").unwrap(), error);
+            }
+            (result, None) => assert!(result.is_ok()),
+            (result, _) => panic!("unexpected result: {:?}", result.err()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_fee_not_covered() {
+    let acc_storage = MockAccStorage::new();
+    let signer = MockSigner;
+    let hasher = MockHasher;
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let pk = SignKey::Ed25519([1; 32]);
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk: VerifyKey::Ed25519([1; 32])
+    };
+    acc_storage.deposit(&address, &Token::new(100, "LOKI")).await.unwrap();
+
+    // the amount alone is covered, the amount with the whole gas limit is not
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 95, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Ed25519([0; 64])
+    };
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = interactor.execute(transaction.clone()).await {
+        assert_eq!(errors.get("body.sender").unwrap(), "you dont have coins");
+    } else {
+        panic!("fee must be covered by the balance");
+    }
+
+    // the fee in another denom is checked against that balance
+    transaction.body.fee.gas_price = Token::new(1, "uusdt");
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    assert!(interactor.execute(transaction.clone()).await.is_err());
+
+    acc_storage.deposit(&address, &Token::new(10, "uusdt")).await.unwrap();
+    assert!(interactor.execute(transaction).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_pool_spending_covered() {
+    let acc_storage = MockAccStorage::new();
+    let mem_pool = MockMemPool::new();
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &mem_pool,
+        app_router: &MockAppRouter,
+        signer: &MockSigner,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let sender = make_request(0, 10).await.body.sender;
+    acc_storage.deposit(&sender, &Token::new(100, "LOKI")).await.unwrap();
+
+    // every transaction may spend 40 and a fee of 10, queued ones included
+    assert_eq!(interactor.execute(make_request(0, 40).await).await.unwrap().state, TxState::PendingConfirmation);
+    assert_eq!(interactor.execute(make_request(2, 40).await).await.unwrap().state, TxState::Queued);
+    let (key, value) = create_error(interactor.execute(make_request(1, 40).await).await).unwrap();
+    assert_eq!((key.as_str(), value.as_str()), ("body.sender", "you dont have coins"));
+    assert_eq!(mem_pool.count().await, 2);
+
+    // released transactions no longer count against the balance
+    mem_pool.release(1).await;
+    assert!(interactor.execute(make_request(1, 40).await).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_chain_id_and_network() {
+    let acc_storage = MockAccStorage::new();
+    let signer = MockSigner;
+    let hasher = MockHasher;
+    let interactor = CreateTransaction {
+        hasher: &hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &MockAppRouter,
+        signer: &signer,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let pk = SignKey::Ed25519([1; 32]);
+    let address = Address {
+        network: "lokichain".to_string(),
+        vk: VerifyKey::Ed25519([1; 32])
+    };
+    acc_storage.deposit(&address, &Token::new(100, "LOKI")).await.unwrap();
+
+    // validly signed for another chain
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-testnet-2".to_string(),
+            sender: address.clone(),
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Ed25519([0; 64])
+    };
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = interactor.execute(transaction.clone()).await {
+        assert_eq!(errors.get("body.chain_id").unwrap(), "chain id is not valid");
+    } else {
+        panic!("transaction of another chain must be rejected");
+    }
+
+    // the sender address of another network
+    transaction.body.chain_id = "lokichain-1".to_string();
+    transaction.body.sender.network = "lokitest".to_string();
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    if let Err(ApplicationError::InvalidData(ref errors)) = interactor.execute(transaction.clone()).await {
+        assert_eq!(errors.get("body.sender").unwrap(), "network is not valid");
+    } else {
+        panic!("address of another network must be rejected");
+    }
+
+    transaction.body.sender.network = "lokichain".to_string();
+    transaction.hash = MockHasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = signer.sign(&transaction.hash.0, &pk).await;
+    assert!(interactor.execute(transaction).await.is_ok());
+}
+
+async fn make_request(nonce: u64, amount: u128) -> CreateTransactionRequest {
+    let mut transaction = CreateTransactionRequest {
+        body: TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: Address {
+                network: "lokichain".to_string(),
+                vk: VerifyKey::Ed25519([1; 32])
+            },
+            amount: Token { value: amount, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce,
+            valid_after: None,
+            valid_until: None,
+            data: AppData { app: "bank".to_string(), operation: "transfer".to_string(), payload: Value::Null },
+        },
+        hash: Hash([0; 32]),
+        signature: Signature::Ed25519([0; 64])
+    };
+    transaction.hash = PlainSha256Hasher.hash(&encode(&transaction.body)).await;
+    transaction.signature = MockSigner.sign(&transaction.hash.0, &SignKey::Ed25519([1; 32])).await;
+    transaction
+}
+
+#[tokio::test]
+async fn test_nonce_sequencing() {
+    let acc_storage = MockAccStorage::new();
+    let mem_pool = MockMemPool::new();
+    let config = NodeConfig { max_nonce_gap: 4, ..Default::default() };
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &mem_pool,
+        app_router: &MockAppRouter,
+        signer: &MockSigner,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config,
+    };
+
+    let request = make_request(0, 10).await;
+    acc_storage.set(
+        request.body.sender.clone(),
+        Account {
+            address: request.body.sender.clone(),
+            nonce: 2,
+            balances: Token { value: 100, denom: "LOKI".to_string() }.into()
+        }
+    ).await;
+
+    let nonce_error = |result: Result<CreateTransactionResult, ApplicationError>| match result {
+        Err(ApplicationError::InvalidData(errors)) => errors.get("body.nonce").cloned(),
+        _ => None,
+    };
+
+    // already executed
+    assert_eq!(nonce_error(interactor.execute(make_request(1, 10).await).await).unwrap(), "nonce is too low");
+
+    let result = interactor.execute(make_request(2, 10).await).await.unwrap();
+    assert_eq!(result.state, TxState::PendingConfirmation);
+    // the same nonce with another body
+    assert_eq!(nonce_error(interactor.execute(make_request(2, 11).await).await).unwrap(), "nonce is already used");
+
+    // waits for nonce 3
+    let queued = interactor.execute(make_request(4, 10).await).await.unwrap();
+    assert_eq!(queued.state, TxState::Queued);
+    let queued_next = interactor.execute(make_request(5, 10).await).await.unwrap();
+    assert_eq!(queued_next.state, TxState::Queued);
+    assert_eq!(nonce_error(interactor.execute(make_request(8, 10).await).await).unwrap(), "nonce is too far ahead");
+
+    // fills the gap, the queued ones become pending
+    let result = interactor.execute(make_request(3, 10).await).await.unwrap();
+    assert_eq!(result.state, TxState::PendingConfirmation);
+    assert_eq!(mem_pool.get(&queued.hash).await.unwrap().state, TxState::PendingConfirmation);
+    assert_eq!(mem_pool.get(&queued_next.hash).await.unwrap().state, TxState::PendingConfirmation);
+
+    let released = mem_pool.release(10).await;
+    assert_eq!(released.iter().map(|tx| tx.transaction.nonce).collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn test_expired_pending_gap() {
+    let acc_storage = MockAccStorage::new();
+    let mem_pool = MockMemPool::new();
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &mem_pool,
+        app_router: &MockAppRouter,
+        signer: &MockSigner,
+        acc_storage: &acc_storage,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let sender = make_request(0, 10).await.body.sender;
+    acc_storage.deposit(&sender, &Token::new(1000, "LOKI")).await.unwrap();
+
+    let mut expiring = make_request(0, 10).await;
+    expiring.body.valid_until = Some(ValidityBound::Height(1));
+    expiring.hash = PlainSha256Hasher.hash(&encode(&expiring.body)).await;
+    expiring.signature = MockSigner.sign(&expiring.hash.0, &SignKey::Ed25519([1; 32])).await;
+    interactor.execute(expiring).await.unwrap();
+    let successor = interactor.execute(make_request(1, 10).await).await.unwrap();
+    assert_eq!(successor.state, TxState::PendingConfirmation);
+
+    // nonce 0 expires before it gets into a block, nonce 1 must wait for it again
+    assert_eq!(mem_pool.evict_expired(2, NOW).await.len(), 1);
+    assert_eq!(mem_pool.get(&successor.hash).await.unwrap().state, TxState::Queued);
+    assert!(mem_pool.release(10).await.is_empty());
+
+    let result = interactor.execute(make_request(0, 11).await).await.unwrap();
+    assert_eq!(result.state, TxState::PendingConfirmation);
+    assert_eq!(mem_pool.get(&successor.hash).await.unwrap().state, TxState::PendingConfirmation);
+    let released = mem_pool.release(10).await;
+    assert_eq!(released.iter().map(|tx| tx.transaction.nonce).collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[tokio::test]
+async fn test_validity_window() {
+    let acc_storage = MockAccStorage::new();
+    let mem_pool = MockMemPool::new();
+    let block_storage = MockBlockStorage::new();
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &mem_pool,
+        app_router: &MockAppRouter,
+        signer: &MockSigner,
+        acc_storage: &acc_storage,
+        block_storage: &block_storage,
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &NodeConfig::default(),
+    };
+
+    let sender = make_request(0, 10).await.body.sender;
+    acc_storage.deposit(&sender, &Token::new(100, "LOKI")).await.unwrap();
+    block_storage.set(Block::new(
+        Hash([0; 32]),
+        10,
+        0,
+        Hash([0; 32]),
+        Hash([0; 32]),
+        Hash([0; 32]),
+        Hash([0; 32]),
+        sender.clone(),
+        Token::new(10, "LOKI"),
+        Signature::Ed25519([0; 64]),
+        vec![]
+    )).await;
+
+    let window_error = |result: Result<CreateTransactionResult, ApplicationError>| match result {
+        Err(ApplicationError::InvalidData(errors)) => errors.into_iter().next(),
+        _ => None,
+    };
+    let request = |valid_after, valid_until| async move {
+        let mut request = make_request(0, 10).await;
+        request.body.valid_after = valid_after;
+        request.body.valid_until = valid_until;
+        request.hash = PlainSha256Hasher.hash(&encode(&request.body)).await;
+        request.signature = MockSigner.sign(&request.hash.0, &SignKey::Ed25519([1; 32])).await;
+        request
+    };
+
+    // the next block is 11
+    let cases = [
+        (None, Some(ValidityBound::Height(10)), ("body.valid_until", "transaction is expired")),
+        (None, Some(ValidityBound::Timestamp(NOW - 1)), ("body.valid_until", "transaction is expired")),
+        (Some(ValidityBound::Timestamp(NOW + 1)), None, ("body.valid_after", "transaction is not valid yet")),
+        (Some(ValidityBound::Height(12)), None, ("body.valid_after", "transaction is not valid yet")),
+        (Some(ValidityBound::Height(12)), Some(ValidityBound::Height(11)), ("body.valid_until", "validity window is empty")),
+    ];
+    for (valid_after, valid_until, (field, error)) in cases {
+        let (key, value) = window_error(interactor.execute(request(valid_after, valid_until).await).await).unwrap();
+        assert_eq!((key.as_str(), value.as_str()), (field, error));
+    }
+
+    let result = interactor.execute(request(Some(ValidityBound::Height(11)), Some(ValidityBound::Timestamp(NOW))).await).await.unwrap();
+    let pending = mem_pool.get(&result.hash).await.unwrap();
+    assert_eq!(pending.transaction.valid_until, Some(ValidityBound::Timestamp(NOW)));
+    assert_eq!(pending.transaction.timestamp, NOW);
+}
+
+/// Request running `app`/`operation`, signed like `[make_request]`
+async fn make_app_request(nonce: u64, amount: Token, app: &str, operation: &str, payload: Value) -> CreateTransactionRequest {
+    let mut request = make_request(nonce, 0).await;
+    request.body.amount = amount;
+    request.body.data = AppData { app: app.to_string(), operation: operation.to_string(), payload };
+    request.hash = PlainSha256Hasher.hash(&encode(&request.body)).await;
+    request.signature = MockSigner.sign(&request.hash.0, &SignKey::Ed25519([1; 32])).await;
+    request
+}
+
+fn create_error(result: Result<CreateTransactionResult, ApplicationError>) -> Option<(String, String)> {
+    match result {
+        Err(ApplicationError::InvalidData(errors)) => errors.into_iter().next(),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_multi_send_debit() {
+    let mocks = MockStores::new();
+    let config = NodeConfig::default();
+    let mut registry = AppRegistry::new(mocks.stores());
+    registry.register(Box::new(BankApp { config: &config }));
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &registry,
+        signer: &MockSigner,
+        acc_storage: &mocks.accounts,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config,
+    };
+    let sender = make_request(0, 0).await.body.sender;
+    // the fee takes 10 LOKI
+    mocks.accounts.deposit(&sender, &Token::new(100, "LOKI")).await.unwrap();
+
+    let receiver = |seed: u8| Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) };
+    let multi_send = |nonce: u64, outputs: Vec<Token>| {
+        let outputs = outputs.into_iter().enumerate()
+            .map(|(index, amount)| serde_json::json!({ "receiver": receiver(index as u8 + 2), "amount": amount }))
+            .collect::<Vec<_>>();
+        make_app_request(nonce, Token::zero("LOKI"), "bank", "multi_send", serde_json::json!({ "outputs": outputs }))
+    };
+
+    // the outputs are checked instead of the unused amount
+    let result = interactor.execute(multi_send(0, vec![Token::new(50, "LOKI"), Token::new(41, "LOKI")]).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.sender".to_string(), "you dont have coins".to_string()));
+    let result = interactor.execute(multi_send(0, vec![Token::new(50, "LOKI"), Token::new(1, "uusdt")]).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.sender".to_string(), "you dont have coins".to_string()));
+    let result = interactor.execute(multi_send(0, vec![Token::new(1, "POINTS")]).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.data.payload.outputs".to_string(), "denom is not registered".to_string()));
+    // reported at the outputs even when the unused amount is the same
+    let output = serde_json::json!({ "receiver": receiver(2), "amount": Token::new(1, "POINTS") });
+    let request = make_app_request(0, Token::new(1, "POINTS"), "bank", "multi_send", serde_json::json!({ "outputs": [output] }));
+    let result = interactor.execute(request.await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.data.payload.outputs".to_string(), "denom is not registered".to_string()));
+    let result = interactor.execute(multi_send(0, vec![Token::new(50, "LOKI"), Token::new(40, "LOKI")]).await).await.unwrap();
+    assert_eq!(result.state, TxState::PendingConfirmation);
+
+    // a transfer still takes its amount, next to the 100 LOKI the pending multi_send may spend
+    mocks.accounts.deposit(&sender, &Token::new(100, "LOKI")).await.unwrap();
+    let transfer = |amount: Token| make_app_request(1, amount, "bank", "transfer", serde_json::json!({ "receiver": receiver(2) }));
+    let result = interactor.execute(transfer(Token::zero("LOKI")).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.amount".to_string(), "amount must be greater than 0".to_string()));
+    let result = interactor.execute(transfer(Token::new(91, "LOKI")).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.sender".to_string(), "you dont have coins".to_string()));
+    assert!(interactor.execute(transfer(Token::new(90, "LOKI")).await).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_token_debit() {
+    let mocks = MockStores::new();
+    let config = NodeConfig::default();
+    let mut registry = AppRegistry::new(mocks.stores());
+    registry.register(Box::new(TokenApp { config: &config }));
+    let interactor = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &MockMemPool::new(),
+        app_router: &registry,
+        signer: &MockSigner,
+        acc_storage: &mocks.accounts,
+        block_storage: &MockBlockStorage::new(),
+        clock: &ManualClock::new(NOW),
+        denom_registry: &mocks.denoms,
+        config: &config,
+    };
+    let admin = make_request(0, 0).await.body.sender;
+    let points = format!("factory/{}/points", admin);
+    mocks.denoms.register(DenomMetadata { base: points.clone(), display: points.clone(), decimals: 0, symbol: "points".to_string() }).await.unwrap();
+    // only enough for the fees of the three transactions
+    mocks.accounts.deposit(&admin, &Token::new(30, "LOKI")).await.unwrap();
+    mocks.accounts.deposit(&admin, &Token::new(5, &points)).await.unwrap();
+
+    let request = |nonce: u64, operation: &'static str, payload: Value| make_app_request(nonce, Token::zero("LOKI"), "token", operation, payload);
+    let result = interactor.execute(request(0, "create_denom", serde_json::json!({ "subdenom": "gems", "max_supply": 100 })).await).await;
+    assert!(result.is_ok());
+    let result = interactor.execute(request(1, "mint", serde_json::json!({ "amount": Token::new(100, &points) })).await).await;
+    assert!(result.is_ok());
+
+    // burning takes the burned tokens from the sender
+    let burn = |value: u128| request(2, "burn", serde_json::json!({ "amount": Token::new(value, &points) }));
+    let result = interactor.execute(burn(6).await).await;
+    assert_eq!(create_error(result).unwrap(), ("body.sender".to_string(), "you dont have coins".to_string()));
+    assert!(interactor.execute(burn(5).await).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_transfer_from_pull_payment() {
+    let mocks = MockStores::new();
+    // free execution, the fee only covers the gas limit of 10
+    let config = NodeConfig {
+        gas_schedule: GasSchedule { read: 0, write: 0, payload_byte: 0, verify_signature: 0 },
+        ..Default::default()
+    };
+    let mut registry = AppRegistry::new(mocks.stores());
+    registry.register(Box::new(BankApp { config: &config }));
+    let mem_pool = MockMemPool::new();
+    let block_storage = MockBlockStorage::new();
+    let clock = ManualClock::new(NOW);
+    let create = CreateTransaction {
+        hasher: &PlainSha256Hasher,
+        mem_pool: &mem_pool,
+        app_router: &registry,
+        signer: &MockSigner,
+        acc_storage: &mocks.accounts,
+        block_storage: &block_storage,
+        clock: &clock,
+        denom_registry: &mocks.denoms,
+        config: &config,
+    };
+    let execute = ExecuteTransaction {
+        app_router: &registry,
+        acc_storage: &mocks.accounts,
+        state_storage: &mocks.state,
+        denom_registry: &mocks.denoms,
+        config: &config
+    };
+
+    // the subscription service only holds LOKI for its fees, the subscriber pays in uusdt
+    let service = make_request(0, 0).await.body.sender;
+    let subscriber = Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([2; 32]) };
+    mocks.accounts.deposit(&service, &Token::new(10, "LOKI")).await.unwrap();
+    mocks.accounts.deposit(&subscriber, &Token::new(10, "LOKI")).await.unwrap();
+    mocks.accounts.deposit(&subscriber, &Token::new(500, "uusdt")).await.unwrap();
+
+    let approve = Transaction::new(
+        Hash([1; 32]),
+        subscriber.clone(),
+        AppData {
+            app: "bank".to_string(),
+            operation: "approve".to_string(),
+            payload: serde_json::json!({ "spender": service, "amount": Token::new(300, "uusdt") })
+        },
+        Token::zero("LOKI"),
+        NOW,
+        Fee::new(10, Token::new(1, "LOKI")),
+        0,
+        Signature::Ed25519([0; 64])
+    );
+    let in_block = |transaction: Transaction| ExecuteTransactionRequest { transaction, height: 1, timestamp: NOW };
+    assert_eq!(execute.execute(in_block(approve)).await.unwrap().state, TxState::Confirmed);
+
+    let pull = |value: u128| make_app_request(0, Token::new(value, "uusdt"), "bank", "transfer_from", serde_json::json!({
+        "owner": subscriber, "receiver": service
+    }));
+    create.execute(pull(200).await).await.unwrap();
+    // taken out of the pool into the block
+    let transaction = mem_pool.release(1).await.remove(0).transaction;
+    let result = execute.execute(in_block(transaction)).await.unwrap();
+    assert_eq!(result.state, TxState::Confirmed);
+
+    let accounts = &mocks.accounts;
+    let balance = |address: Address| async move { accounts.get(&address).await.unwrap().balances.get("uusdt").value };
+    assert_eq!((balance(subscriber.clone()).await, balance(service.clone()).await), (300, 200));
+
+    // the allowance left is checked on execution, not on creation
+    create.execute(make_app_request(1, Token::new(101, "uusdt"), "bank", "transfer_from", serde_json::json!({
+        "owner": subscriber, "receiver": service
+    })).await).await.unwrap();
+    let transaction = mem_pool.release(1).await.remove(0).transaction;
+    assert_eq!(execute.execute(in_block(transaction)).await.unwrap().state, TxState::Reverted);
+    assert_eq!(balance(subscriber.clone()).await, 300);
+}
@@ -0,0 +1,275 @@
+use crate::application::common::acc_storage::tests::MockAccStorage;
+use crate::application::common::acc_storage::AccStorage;
+use crate::application::common::app_router::tests::MockAppRouter;
+use crate::application::common::app_router::AppRouter;
+use crate::application::common::config::NodeConfig;
+use crate::application::common::context::ExecutionContext;
+use crate::application::common::denom_registry::tests::MockDenomRegistry;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::gas::GasSchedule;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::state_storage::tests::MockStateStorage;
+use crate::application::transaction::execute::{ExecuteTransaction, ExecuteTransactionRequest};
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::address::Address;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::fee::Fee;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::receipt::Event;
+use crate::domain::models::signature::{Signature, VerifyKey};
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::{Transaction, TxState};
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Gas of the null payload and the single signature
+const INTRINSIC_GAS: u64 = 3 + 4;
+/// Gas of the mock transfer, one read and one write
+const TRANSFER_GAS: u64 = 1 + 2;
+
+fn config() -> NodeConfig {
+    NodeConfig {
+        gas_schedule: GasSchedule { read: 1, write: 2, payload_byte: 3, verify_signature: 4 },
+        ..Default::default()
+    }
+}
+
+fn address(seed: u8) -> Address {
+    Address {
+        network: "lokichain".to_string(),
+        vk: VerifyKey::Ed25519([seed; 32])
+    }
+}
+
+fn make_transaction(operation: &str, fee: Fee, nonce: u64) -> Transaction {
+    Transaction::new(
+        Hash([0; 32]),
+        address(1),
+        AppData { app: "bank".to_string(), operation: operation.to_string(), payload: Value::Null },
+        Token::new(10, "LOKI"),
+        0,
+        fee,
+        nonce,
+        Signature::Ed25519([0; 64])
+    )
+}
+
+/// Applies `transaction` in the first block
+fn in_block(transaction: Transaction) -> ExecuteTransactionRequest {
+    ExecuteTransactionRequest { transaction, height: 1, timestamp: 0 }
+}
+
+async fn balance(acc_storage: &MockAccStorage, denom: &str) -> u128 {
+    acc_storage.get(&address(1)).await.unwrap().balances.get(denom).value
+}
+
+/// Credits another account, then fails
+struct FailingRouter;
+
+#[async_trait]
+impl AppRouter for FailingRouter {
+    async fn is_exist(&self, _app_name: &str, _operation: &str) -> bool {
+        true
+    }
+
+    async fn validate(&self, _data: &AppData) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    async fn sender_debit(&self, _data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        Ok(vec![("body.amount", amount.clone())])
+    }
+
+    async fn execute(&self, transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        ctx.deposit(&address(2), &transaction.amount).await?;
+        Err(ApplicationError::NotFound("receiver".to_string()))
+    }
+
+    async fn query(&self, app_name: &str, _query: &str, _payload: &Value) -> Result<Value, ApplicationError> {
+        Err(ApplicationError::NotFound(format!("app {}", app_name)))
+    }
+}
+
+/// Emits the block position the operation runs at
+struct PositionRouter;
+
+#[async_trait]
+impl AppRouter for PositionRouter {
+    async fn is_exist(&self, _app_name: &str, _operation: &str) -> bool {
+        true
+    }
+
+    async fn validate(&self, _data: &AppData) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    async fn sender_debit(&self, _data: &AppData, amount: &Token) -> Result<Vec<(&'static str, Token)>, ApplicationError> {
+        Ok(vec![("body.amount", amount.clone())])
+    }
+
+    async fn execute(&self, _transaction: &Transaction, ctx: &mut ExecutionContext<'_>) -> Result<(), ApplicationError> {
+        let event = Event::new("test", "position").with("height", ctx.height).with("timestamp", ctx.timestamp);
+        ctx.emit(event);
+        Ok(())
+    }
+
+    async fn query(&self, app_name: &str, _query: &str, _payload: &Value) -> Result<Value, ApplicationError> {
+        Err(ApplicationError::NotFound(format!("app {}", app_name)))
+    }
+}
+
+#[tokio::test]
+async fn test_refund_unused_gas() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+    acc_storage.deposit(&address(1), &Token::new(1000, "uusdt")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &MockAppRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(20, Token::new(3, "uusdt")), 0))).await.unwrap();
+    assert_eq!(result.state, TxState::Confirmed);
+    assert_eq!(result.gas_used, INTRINSIC_GAS + TRANSFER_GAS);
+    assert_eq!(result.fee, Token::new(3 * (INTRINSIC_GAS + TRANSFER_GAS) as u128, "uusdt"));
+
+    assert_eq!(balance(&acc_storage, "uusdt").await, 1000 - 3 * (INTRINSIC_GAS + TRANSFER_GAS) as u128);
+    assert_eq!(balance(&acc_storage, "LOKI").await, 90);
+
+    let receipt = result.into_receipt(Hash([5; 32]), 7, 2);
+    assert_eq!((receipt.tx_hash, receipt.block_height, receipt.index), (Hash([5; 32]), 7, 2));
+    assert_eq!(receipt.gas_used, INTRINSIC_GAS + TRANSFER_GAS);
+    assert!(receipt.error.is_none());
+    assert_eq!(receipt.events.len(), 1);
+    assert_eq!(receipt.events[0].attributes["amount"], "10");
+}
+
+#[tokio::test]
+async fn test_out_of_gas() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &MockAppRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    // runs out on the write of the transfer
+    let gas_limit = INTRINSIC_GAS + TRANSFER_GAS - 1;
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(gas_limit, Token::new(2, "LOKI")), 0))).await.unwrap();
+    assert_eq!(result.state, TxState::Reverted);
+    assert_eq!(result.gas_used, gas_limit);
+    assert_eq!(result.error.unwrap().code, ApplicationError::OutOfGas(gas_limit).code());
+    assert_eq!(result.fee, Token::new(2 * gas_limit as u128, "LOKI"));
+    assert_eq!(balance(&acc_storage, "LOKI").await, 100 - 2 * gas_limit as u128);
+
+    // not even enough for the payload
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(1, Token::new(2, "LOKI")), 1))).await.unwrap();
+    assert_eq!(result.state, TxState::Reverted);
+    assert_eq!(result.gas_used, 1);
+}
+
+#[tokio::test]
+async fn test_failed_operation_reverts() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &FailingRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(20, Token::new(1, "LOKI")), 0))).await.unwrap();
+    assert_eq!(result.state, TxState::Reverted);
+    // the used gas is paid, the rest is refunded
+    assert_eq!(result.gas_used, INTRINSIC_GAS + TRANSFER_GAS);
+    assert_eq!(balance(&acc_storage, "LOKI").await, 100 - (INTRINSIC_GAS + TRANSFER_GAS) as u128);
+    assert!(acc_storage.get(&address(2)).await.is_none());
+    let error = result.error.unwrap();
+    assert_eq!(error.code, ApplicationError::NotFound(String::new()).code());
+    assert_eq!(error.message, "Not found: receiver");
+    assert!(result.events.is_empty());
+
+    let interactor = ExecuteTransaction {
+        app_router: &MockAppRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+    let result = interactor.execute(in_block(make_transaction("unknown", Fee::new(20, Token::new(1, "LOKI")), 1))).await.unwrap();
+    assert_eq!(result.state, TxState::Reverted);
+    assert_eq!(result.gas_used, INTRINSIC_GAS);
+}
+
+#[tokio::test]
+async fn test_fee_not_covered() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(10, "LOKI")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &MockAppRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(20, Token::new(1, "LOKI")), 0))).await;
+    assert!(matches!(result, Err(ApplicationError::Domain(DomainError::Underflow))));
+    assert_eq!(balance(&acc_storage, "LOKI").await, 10);
+}
+
+#[tokio::test]
+async fn test_nonce() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &FailingRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    let result = interactor.execute(in_block(make_transaction("transfer", Fee::new(20, Token::new(1, "LOKI")), 1))).await;
+    assert!(matches!(result, Err(ApplicationError::InvalidData(_))));
+    assert_eq!(acc_storage.get(&address(1)).await.unwrap().nonce, 0);
+    assert_eq!(balance(&acc_storage, "LOKI").await, 100);
+
+    // the nonce is used by a reverted transaction too, so it can not be replayed
+    let transaction = make_transaction("transfer", Fee::new(20, Token::new(1, "LOKI")), 0);
+    let result = interactor.execute(in_block(transaction.clone())).await.unwrap();
+    assert_eq!(result.state, TxState::Reverted);
+    assert_eq!(acc_storage.get(&address(1)).await.unwrap().nonce, 1);
+    assert!(interactor.execute(in_block(transaction)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_block_position() {
+    let acc_storage = MockAccStorage::new();
+    acc_storage.deposit(&address(1), &Token::new(100, "LOKI")).await.unwrap();
+    let interactor = ExecuteTransaction {
+        app_router: &PositionRouter,
+        acc_storage: &acc_storage,
+        state_storage: &MockStateStorage::new(),
+        denom_registry: &MockDenomRegistry::new(),
+        config: &config()
+    };
+
+    // the operation runs at the position of the block the transaction is applied in
+    let request = ExecuteTransactionRequest {
+        transaction: make_transaction("transfer", Fee::new(20, Token::new(1, "LOKI")), 0),
+        height: 11,
+        timestamp: 1_700_000_005
+    };
+    let result = interactor.execute(request).await.unwrap();
+    assert_eq!(result.events[0].attributes["height"], "11");
+    assert_eq!(result.events[0].attributes["timestamp"], "1700000005");
+}
@@ -1 +1,2 @@
-pub mod create_tests;
\ No newline at end of file
+pub mod create_tests;
+pub mod execute_tests;
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DomainError {
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Token overflow")]
+    Overflow,
+    #[error("Token underflow")]
+    Underflow,
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Denom mismatch: {0} and {1}")]
+    DenomMismatch(String, String),
+    #[error("Invalid denom: {0}")]
+    InvalidDenom(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Supply cap exceeded: {0}")]
+    SupplyCapExceeded(String),
+}
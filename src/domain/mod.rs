@@ -1,2 +1,3 @@
+pub mod exceptions;
 pub mod models;
-pub mod services;
\ No newline at end of file
+pub mod services;
@@ -1,14 +1,228 @@
+use crate::domain::exceptions::DomainError;
 use crate::domain::models::address::Address;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt::Display;
 use crate::domain::models::token::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Balances of an account, one amount per denom
+///
+/// Serialized as a `denom -> value` map. Zero amounts are never stored,
+/// so two balances holding the same tokens are always equal
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, u128>")]
+pub struct Balances(BTreeMap<String, u128>);
+
+impl Balances {
+    pub fn new() -> Self {
+        Balances(BTreeMap::new())
+    }
+
+    /// Amount of `denom`, zero if the account does not hold it
+    pub fn get(&self, denom: &str) -> Token {
+        Token::new(self.0.get(denom).copied().unwrap_or(0), denom)
+    }
+
+    /// Balances are left unchanged on overflow
+    pub fn add(&mut self, amount: &Token) -> Result<(), DomainError> {
+        let balance = self.get(&amount.denom).checked_add(amount)?;
+        self.insert(balance);
+        Ok(())
+    }
+
+    /// Balances are left unchanged on underflow
+    pub fn sub(&mut self, amount: &Token) -> Result<(), DomainError> {
+        let balance = self.get(&amount.denom).checked_sub(amount)?;
+        self.insert(balance);
+        Ok(())
+    }
+
+    fn insert(&mut self, balance: Token) {
+        if balance.is_zero() {
+            self.0.remove(&balance.denom);
+        } else {
+            self.0.insert(balance.denom, balance.value);
+        }
+    }
+
+    /// Non zero balances sorted by denom
+    pub fn iter(&self) -> impl Iterator<Item = Token> + '_ {
+        self.0.iter().map(|(denom, value)| Token::new(*value, denom))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl TryFrom<BTreeMap<String, u128>> for Balances {
+    type Error = DomainError;
+
+    fn try_from(map: BTreeMap<String, u128>) -> Result<Self, Self::Error> {
+        map.into_iter().map(|(denom, value)| Token { value, denom }).collect()
+    }
+}
+
+impl From<Token> for Balances {
+    fn from(token: Token) -> Self {
+        let mut balances = Balances::new();
+        balances.insert(token);
+        balances
+    }
+}
+
+/// Sums the tokens per denom, fails on overflow
+impl FromIterator<Token> for Result<Balances, DomainError> {
+    fn from_iter<I: IntoIterator<Item = Token>>(tokens: I) -> Self {
+        let mut balances = Balances::new();
+        for token in tokens {
+            balances.add(&token)?;
+        }
+        Ok(balances)
+    }
+}
+
+
+/// Serde of `[Balances]` with values as decimal strings, see `[decimal]`
+///
+/// [decimal]: crate::domain::models::token::decimal
+pub mod decimal_balances {
+    use super::Balances;
+    use crate::domain::models::token::{decimal, Token};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize)]
+    struct DecimalValue(#[serde(with = "decimal")] u128);
+
+    pub fn serialize<S: Serializer>(balances: &Balances, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(balances.0.iter().map(|(denom, value)| (denom, DecimalValue(*value))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Balances, D::Error> {
+        BTreeMap::<String, DecimalValue>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(denom, DecimalValue(value))| Token { value, denom })
+            .collect::<Result<Balances, _>>()
+            .map_err(de::Error::custom)
+    }
+}
 
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "AccountRecord")]
 pub struct Account {
     /// account address
     pub address: Address,
-    /// last transaction number
+    /// number of executed transactions, the nonce of the next one
     pub nonce: u64,
-    /// account balance
-    pub balance: Token,
+    /// account balances
+    pub balances: Balances,
+}
+
+/// Stored form of `[Account]`
+///
+/// Accounts stored before multi-denom balances have a single `balance` token,
+/// it is merged into `balances` when the account is loaded
+#[derive(Deserialize)]
+struct AccountRecord {
+    address: Address,
+    nonce: u64,
+    #[serde(default)]
+    balances: Balances,
+    #[serde(default)]
+    balance: Option<Token>,
+}
+
+impl TryFrom<AccountRecord> for Account {
+    type Error = DomainError;
+
+    fn try_from(record: AccountRecord) -> Result<Self, Self::Error> {
+        let mut balances = record.balances;
+        if let Some(balance) = record.balance {
+            balances.add(&balance)?;
+        }
+        Ok(Account { address: record.address, nonce: record.nonce, balances })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::signature::VerifyKey;
+
+    fn address() -> Address {
+        Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) }
+    }
+
+    #[test]
+    fn test_balances() {
+        let mut balances = Balances::from(Token::new(100, "LOKI"));
+        balances.add(&Token::new(5, "uusdt")).unwrap();
+        assert_eq!(balances.get("LOKI"), Token::new(100, "LOKI"));
+        assert_eq!(balances.get("uusdt"), Token::new(5, "uusdt"));
+        assert_eq!(balances.get("uatom"), Token::zero("uatom"));
+
+        assert!(matches!(balances.sub(&Token::new(6, "uusdt")), Err(DomainError::Underflow)));
+        assert!(matches!(balances.add(&Token::new(u128::MAX, "LOKI")), Err(DomainError::Overflow)));
+        assert_eq!(balances.iter().collect::<Vec<_>>(), vec![Token::new(100, "LOKI"), Token::new(5, "uusdt")]);
+
+        // an emptied denom is dropped
+        balances.sub(&Token::new(5, "uusdt")).unwrap();
+        assert_eq!(balances, Balances::from(Token::new(100, "LOKI")));
+        assert!(Balances::from(Token::zero("LOKI")).is_empty());
+    }
+
+    #[test]
+    fn test_serialize_account() {
+        let mut balances = Balances::from(Token::new(100, "LOKI"));
+        balances.add(&Token::new(u128::MAX, "uusdt")).unwrap();
+        let account = Account { address: address(), nonce: 3, balances };
+
+        let serialized = serde_json::to_string(&account).unwrap();
+        assert!(serialized.contains(r#""balances":{"LOKI":100,"uusdt":340282366920938463463374607431768211455}"#));
+        assert_eq!(serde_json::from_str::<Account>(&serialized).unwrap(), account);
+    }
+
+    #[test]
+    fn test_decimal_balances() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Response(#[serde(with = "decimal_balances")] Balances);
+
+        let mut balances = Balances::from(Token::new(100, "LOKI"));
+        balances.add(&Token::new(u128::MAX, "uusdt")).unwrap();
+        let value = serde_json::to_value(Response(balances.clone())).unwrap();
+        assert_eq!(value, serde_json::json!({ "LOKI": "100", "uusdt": u128::MAX.to_string() }));
+        assert_eq!(serde_json::from_value::<Response>(value).unwrap(), Response(balances));
+
+        // zero amounts are dropped as with numeric values
+        let value = serde_json::json!({ "LOKI": "0" });
+        assert_eq!(serde_json::from_value::<Response>(value).unwrap(), Response(Balances::new()));
+        assert!(serde_json::from_value::<Response>(serde_json::json!({ "LOKI": "-1" })).is_err());
+    }
+
+    #[test]
+    fn test_migrate_single_balance() {
+        let legacy = serde_json::json!({
+            "address": address().to_string(),
+            "nonce": 3,
+            "balance": {"value": 100, "denom": "LOKI"}
+        });
+        let account = serde_json::from_value::<Account>(legacy).unwrap();
+        assert_eq!(account.balances, Balances::from(Token::new(100, "LOKI")));
+
+        // a half migrated record keeps both
+        let mixed = serde_json::json!({
+            "address": address().to_string(),
+            "nonce": 3,
+            "balance": {"value": 100, "denom": "LOKI"},
+            "balances": {"LOKI": 1, "uusdt": 5}
+        });
+        let account = serde_json::from_value::<Account>(mixed).unwrap();
+        assert_eq!(account.balances.get("LOKI"), Token::new(101, "LOKI"));
+        assert_eq!(account.balances.get("uusdt"), Token::new(5, "uusdt"));
+    }
 }
@@ -1,9 +1,17 @@
 use bech32::{Bech32m, Hrp};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{Display, Formatter};
+use crate::domain::exceptions::DomainError;
 use crate::domain::models::signature::VerifyKey;
 
-#[derive(Clone)]
+/// Offset of hardened indexes in a derivation path
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// BIP-44 derivation path of an address
+///
+/// Printed and parsed as `m/purpose'/coin_type'/account'/change'/address_index'`.
+/// All levels are hardened, because SLIP-10 Ed25519 derivation supports only hardened keys
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RawAddress {
     /// network tag; ex: lokichain
     pub network: String,
@@ -24,6 +32,61 @@ pub struct RawAddress {
     pub address_index: u32
 }
 
+impl RawAddress {
+    /// Parses a path like `m/44'/1237'/0'/0'/0'`; `h` is accepted instead of `'`
+    pub fn from_path(network: &str, path: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: &str| DomainError::InvalidPath(format!("{}: {}", path, reason));
+
+        let mut levels = path.split('/');
+        if levels.next() != Some("m") {
+            return Err(invalid("must start with `m`"));
+        }
+
+        let mut indexes = vec![];
+        for level in levels {
+            let index = level
+                .strip_suffix('\'')
+                .or_else(|| level.strip_suffix('h'))
+                .ok_or_else(|| invalid("all levels must be hardened"))?;
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid("index must be a number"));
+            }
+            match index.parse::<u32>() {
+                Ok(index) if index < HARDENED => indexes.push(index),
+                _ => return Err(invalid("index is too large")),
+            }
+        }
+
+        let [purpose, coin_type, account, change, address_index] = <[u32; 5]>::try_from(indexes)
+            .map_err(|_| invalid("expected 5 levels"))?;
+
+        Ok(RawAddress {
+            network: network.to_string(),
+            purpose,
+            coin_type,
+            account,
+            change,
+            address_index
+        })
+    }
+
+    /// Child indexes of the path with the hardened bit set
+    pub fn indexes(&self) -> [u32; 5] {
+        [self.purpose, self.coin_type, self.account, self.change, self.address_index]
+            .map(|index| index | HARDENED)
+    }
+}
+
+impl Display for RawAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "m/{}'/{}'/{}'/{}'/{}'",
+            self.purpose, self.coin_type, self.account, self.change, self.address_index
+        )
+    }
+}
+
 
 #[derive(Clone, PartialEq, Eq, Debug, core::hash::Hash)]
 pub struct Address {
@@ -37,7 +100,7 @@ pub struct Address {
 impl Display for Address {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let hrp = Hrp::parse(&self.network).map_err(|_| std::fmt::Error)?;
-        let encoded = bech32::encode::<Bech32m>(hrp, &self.vk.0).map_err(serde::ser::Error::custom)?;
+        let encoded = bech32::encode::<Bech32m>(hrp, &self.vk.to_bytes()).map_err(|_| std::fmt::Error)?;
         write!(f, "{}", encoded)
     }
 }
@@ -60,9 +123,9 @@ impl<'de> Deserialize<'de> for Address {
         let (hrp, hash) = bech32::decode(&s).map_err(serde::de::Error::custom)?;
         Ok(Address {
             network: hrp.to_string(),
-            vk: VerifyKey(<[u8; 32]>::try_from(hash).map_err(
-                |err| serde::de::Error::custom(format!("Err parse vk: {:?}", err))
-            )?)
+            vk: VerifyKey::from_bytes(&hash).map_err(
+                |err| serde::de::Error::custom(format!("Err parse vk: {}", err))
+            )?
         })
     }
 }
@@ -71,12 +134,13 @@ impl<'de> Deserialize<'de> for Address {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::models::signature::MultisigKey;
 
     #[test]
     fn serialize_deserialize_address() {
         let original_address = Address {
             network: "lokichain".to_string(),
-            vk: VerifyKey([0; 32])
+            vk: VerifyKey::Ed25519([0; 32])
         };
 
         let serialized = serde_json::to_string(&original_address).unwrap();
@@ -85,8 +149,77 @@ mod tests {
 
         let new_address = Address {
             network: "lokichain".to_string(),
-            vk: VerifyKey([1; 32])
+            vk: VerifyKey::Ed25519([1; 32])
         };
         assert_ne!(new_address, deserialized_address);
     }
+
+    #[test]
+    fn address_schemes() {
+        // Ed25519 addresses are encoded without a scheme tag
+        let ed25519 = Address {
+            network: "lokichain".to_string(),
+            vk: VerifyKey::Ed25519([1; 32])
+        };
+        assert_eq!(
+            ed25519.to_string(),
+            "lokichain1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqslwwry3"
+        );
+
+        let secp256k1 = Address {
+            network: "lokichain".to_string(),
+            vk: VerifyKey::Secp256k1([2; 33])
+        };
+        let serialized = serde_json::to_string(&secp256k1).unwrap();
+        assert_eq!(serde_json::from_str::<Address>(&serialized).unwrap(), secp256k1);
+        assert_ne!(secp256k1.to_string(), ed25519.to_string());
+
+        let keys = vec![VerifyKey::Ed25519([1; 32]), VerifyKey::Secp256k1([2; 33]), VerifyKey::Ed25519([3; 32])];
+        let multisig = Address {
+            network: "lokichain".to_string(),
+            vk: MultisigKey::new(2, keys.clone()).unwrap().to_verify_key()
+        };
+        let serialized = serde_json::to_string(&multisig).unwrap();
+        assert_eq!(serde_json::from_str::<Address>(&serialized).unwrap(), multisig);
+
+        // the same set of keys in any order is the same address
+        let reordered = MultisigKey::new(2, keys.into_iter().rev().collect()).unwrap().to_verify_key();
+        assert_eq!(multisig.vk, reordered);
+    }
+
+    #[test]
+    fn parse_print_path() {
+        let raw = RawAddress::from_path("lokichain", "m/44'/1237'/2'/0'/7'").unwrap();
+        assert_eq!(raw, RawAddress {
+            network: "lokichain".to_string(),
+            purpose: 44,
+            coin_type: 1237,
+            account: 2,
+            change: 0,
+            address_index: 7
+        });
+        assert_eq!(raw.to_string(), "m/44'/1237'/2'/0'/7'");
+        assert_eq!(RawAddress::from_path("lokichain", "m/44h/1237h/2h/0h/7h").unwrap(), raw);
+        assert_eq!(raw.indexes()[0], 44 | HARDENED);
+    }
+
+    #[test]
+    fn parse_invalid_path() {
+        let invalid = [
+            "",
+            "44'/1237'/0'/0'/0'",
+            "m/44'/1237'/0'/0'",
+            "m/44'/1237'/0'/0'/0'/0'",
+            "m/44'/1237'/0'/0/0'",
+            "m/44'/1237'/0'/0'/+1'",
+            "m/44'/1237'/0'/0'/'",
+            "m/44'/1237'/0'/0'/2147483648'",
+        ];
+        for path in invalid {
+            assert!(
+                matches!(RawAddress::from_path("lokichain", path), Err(DomainError::InvalidPath(_))),
+                "{path} must be rejected"
+            );
+        }
+    }
 }
\ No newline at end of file
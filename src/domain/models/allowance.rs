@@ -0,0 +1,58 @@
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::address::Address;
+use crate::domain::models::token::{decimal_token, Token};
+use crate::domain::models::validity::{ValidityBound, ValidityWindow};
+use serde::{Deserialize, Serialize};
+
+/// Tokens of a single denom `owner` lets `spender` transfer on its behalf
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allowance {
+    pub owner: Address,
+    pub spender: Address,
+    /// left to spend
+    #[serde(with = "decimal_token")]
+    pub amount: Token,
+    /// last block the allowance can be spent in, never expires if not set
+    pub expiry: Option<ValidityBound>
+}
+
+impl Allowance {
+    /// The block at `height` and `timestamp` is past the expiry
+    pub fn is_expired(&self, height: u64, timestamp: u64) -> bool {
+        ValidityWindow { valid_after: None, valid_until: self.expiry }.is_expired(height, timestamp)
+    }
+
+    /// Amount is left unchanged if it is not enough
+    pub fn spend(&mut self, amount: &Token) -> Result<(), DomainError> {
+        self.amount = self.amount.checked_sub(amount)?;
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::signature::VerifyKey;
+
+    #[test]
+    fn test_spend_and_expiry() {
+        let address = |seed: u8| Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([seed; 32]) };
+        let mut allowance = Allowance {
+            owner: address(1),
+            spender: address(2),
+            amount: Token::new(100, "LOKI"),
+            expiry: Some(ValidityBound::Height(10))
+        };
+
+        allowance.spend(&Token::new(60, "LOKI")).unwrap();
+        assert_eq!(allowance.spend(&Token::new(41, "LOKI")), Err(DomainError::Underflow));
+        assert!(allowance.spend(&Token::new(1, "uusdt")).is_err());
+        assert_eq!(allowance.amount, Token::new(40, "LOKI"));
+
+        assert!(!allowance.is_expired(10, 0));
+        assert!(allowance.is_expired(11, 0));
+        allowance.expiry = None;
+        assert!(!allowance.is_expired(u64::MAX, u64::MAX));
+    }
+}
@@ -1,5 +1,3 @@
-use chrono::Utc;
-
 use super::transaction::Transaction;
 use crate::domain::models::address::Address;
 use crate::domain::models::hash::Hash;
@@ -10,37 +8,45 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone)]
 pub struct BlockHeader {
     /// block hash
-    hash: Hash,
+    pub hash: Hash,
     /// Block number
-    height: u64,
+    pub height: u64,
     /// Time of creation
-    timestamp: u64,
+    pub timestamp: u64,
     /// hash of the previous block
-    pre_hash: Hash,
+    pub pre_hash: Hash,
     /// merkle root transaction hash
-    merkle_root: Hash,
+    pub merkle_root: Hash,
+    /// merkle root of the encoded receipts, in transaction order
+    pub receipts_root: Hash,
+    /// root of the state tree of accounts, app state and denoms after applying this block
+    pub state_root: Hash,
     /// The address of the validator that issued this block
-    validator: Address,
+    pub validator: Address,
     /// the reward this validator received for issuing a block
-    reward: Token,
+    pub reward: Token,
     /// validator signature
-    signature: Signature
+    pub signature: Signature
 }
 
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Block {
-    header: BlockHeader,
-    transactions: Vec<Transaction>
+    pub header: BlockHeader,
+    pub transactions: Vec<Transaction>
 }
 
 
 impl Block {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         hash: Hash,
         height: u64,
+        timestamp: u64,
         pre_hash: Hash,
         merkle_root: Hash,
+        receipts_root: Hash,
+        state_root: Hash,
         validator: Address,
         reward: Token,
         sign: Signature,
@@ -52,10 +58,12 @@ impl Block {
                 height,
                 pre_hash,
                 merkle_root,
+                receipts_root,
+                state_root,
                 validator,
                 reward,
                 signature: sign,
-                timestamp: Utc::now().timestamp() as u64,
+                timestamp,
             },
             transactions
         }
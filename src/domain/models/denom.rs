@@ -0,0 +1,198 @@
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::token::Token;
+use serde::{Deserialize, Serialize};
+
+/// Longest base or display unit, fits the `factory/<creator>/<subdenom>` units of the `token` app
+pub const MAX_UNIT_LEN: usize = 128;
+
+/// Largest supported `decimals`, `10^38` still fits into `u128`
+pub const MAX_DECIMALS: u8 = 38;
+
+/// Denomination metadata
+///
+/// `[Token]` values are always stored in the `base` unit. The `display` unit is
+/// `10^decimals` base units, for example 1 LOKI = 1_000_000 uloki
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenomMetadata {
+    /// smallest unit, the `[Token]` denom; ex: uloki
+    pub base: String,
+    /// unit shown to users; ex: LOKI
+    pub display: String,
+    /// number of base unit digits in one display unit
+    pub decimals: u8,
+    /// ticker or sign for user interfaces; ex: Ł
+    pub symbol: String
+}
+
+fn is_valid_unit(unit: &str) -> bool {
+    !unit.is_empty()
+        && unit.len() <= MAX_UNIT_LEN
+        && unit.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'/' || b == b'-' || b == b'_')
+}
+
+impl DenomMetadata {
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if !is_valid_unit(&self.base) || !is_valid_unit(&self.display) {
+            return Err(DomainError::InvalidDenom(format!(
+                "{} / {}: units must be 1 to {} ascii letters, digits, `/`, `-` or `_`",
+                self.base, self.display, MAX_UNIT_LEN
+            )));
+        }
+        if self.decimals > MAX_DECIMALS {
+            return Err(DomainError::InvalidDenom(format!("{}: at most {} decimals", self.base, MAX_DECIMALS)));
+        }
+        if self.base == self.display && self.decimals != 0 {
+            return Err(DomainError::InvalidDenom(format!("{}: display unit equal to base must have 0 decimals", self.base)));
+        }
+        Ok(())
+    }
+
+    fn scale(&self) -> u128 {
+        10u128.pow(self.decimals as u32)
+    }
+
+    /// Converts `value` given in `unit` (base or display) into a base unit `[Token]`
+    ///
+    /// The value is a plain decimal number, the base unit does not accept a fraction
+    pub fn to_token(&self, value: &str, unit: &str) -> Result<Token, DomainError> {
+        let invalid = |reason: &str| DomainError::InvalidAmount(format!("{} {}: {}", value, unit, reason));
+
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (value, ""),
+        };
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) || value.ends_with('.') {
+            return Err(invalid("must be a decimal number"));
+        }
+
+        let decimals = if unit == self.display {
+            self.decimals as usize
+        } else if unit == self.base {
+            0
+        } else {
+            return Err(invalid(&format!("unit must be {} or {}", self.display, self.base)));
+        };
+        if fraction.len() > decimals {
+            return Err(invalid(&format!("at most {} decimals", decimals)));
+        }
+
+        let parse = |digits: &str| digits.parse::<u128>().map_err(|_| DomainError::Overflow);
+        let integer = parse(integer)?;
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            parse(fraction)? * 10u128.pow((decimals - fraction.len()) as u32)
+        };
+        let value = integer
+            .checked_mul(10u128.pow(decimals as u32))
+            .and_then(|value| value.checked_add(fraction))
+            .ok_or(DomainError::Overflow)?;
+
+        Ok(Token::new(value, &self.base))
+    }
+
+    /// Formats a base unit `[Token]` in the display unit, without trailing zeros; ex: `1.25 LOKI`
+    pub fn format(&self, token: &Token) -> Result<String, DomainError> {
+        if token.denom != self.base {
+            return Err(DomainError::DenomMismatch(token.denom.clone(), self.base.clone()));
+        }
+
+        let integer = token.value / self.scale();
+        let fraction = token.value % self.scale();
+        if fraction == 0 {
+            return Ok(format!("{} {}", integer, self.display));
+        }
+        let fraction = format!("{:0width$}", fraction, width = self.decimals as usize);
+        Ok(format!("{}.{} {}", integer, fraction.trim_end_matches('0'), self.display))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loki() -> DenomMetadata {
+        DenomMetadata {
+            base: "uloki".to_string(),
+            display: "LOKI".to_string(),
+            decimals: 6,
+            symbol: "Ł".to_string()
+        }
+    }
+
+    #[test]
+    fn test_to_token() {
+        let loki = loki();
+        assert_eq!(loki.to_token("1.25", "LOKI"), Ok(Token::new(1_250_000, "uloki")));
+        assert_eq!(loki.to_token("0.000001", "LOKI"), Ok(Token::new(1, "uloki")));
+        assert_eq!(loki.to_token("7", "LOKI"), Ok(Token::new(7_000_000, "uloki")));
+        assert_eq!(loki.to_token("1250000", "uloki"), Ok(Token::new(1_250_000, "uloki")));
+        assert_eq!(loki.to_token("007.50", "LOKI"), Ok(Token::new(7_500_000, "uloki")));
+    }
+
+    #[test]
+    fn test_to_token_invalid() {
+        let loki = loki();
+        let invalid = [
+            ("", "LOKI"),
+            ("1.", "LOKI"),
+            (".5", "LOKI"),
+            ("-1", "LOKI"),
+            ("+1", "LOKI"),
+            ("1e6", "LOKI"),
+            ("1,5", "LOKI"),
+            ("1.5.5", "LOKI"),
+            ("0.0000001", "LOKI"),
+            ("1.5", "uloki"),
+            ("1", "USDT"),
+        ];
+        for (value, unit) in invalid {
+            assert!(
+                matches!(loki.to_token(value, unit), Err(DomainError::InvalidAmount(_))),
+                "{value} {unit} must be rejected"
+            );
+        }
+        assert_eq!(loki.to_token(&u128::MAX.to_string(), "LOKI"), Err(DomainError::Overflow));
+        assert_eq!(loki.to_token("1000000000000000000000000000000000000000", "uloki"), Err(DomainError::Overflow));
+    }
+
+    #[test]
+    fn test_format() {
+        let loki = loki();
+        assert_eq!(loki.format(&Token::new(1_250_000, "uloki")).unwrap(), "1.25 LOKI");
+        assert_eq!(loki.format(&Token::new(1, "uloki")).unwrap(), "0.000001 LOKI");
+        assert_eq!(loki.format(&Token::new(3_000_000, "uloki")).unwrap(), "3 LOKI");
+        assert_eq!(loki.format(&Token::zero("uloki")).unwrap(), "0 LOKI");
+        assert!(loki.format(&Token::new(1, "LOKI")).is_err());
+
+        let max = Token::new(u128::MAX, "uloki");
+        let formatted = loki.format(&max).unwrap();
+        let (value, unit) = formatted.split_once(' ').unwrap();
+        assert_eq!(loki.to_token(value, unit), Ok(max));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(loki().validate().is_ok());
+
+        let mut metadata = loki();
+        metadata.decimals = MAX_DECIMALS + 1;
+        assert!(metadata.validate().is_err());
+
+        let mut metadata = loki();
+        metadata.display = "uloki".to_string();
+        assert!(metadata.validate().is_err());
+        metadata.decimals = 0;
+        assert!(metadata.validate().is_ok());
+
+        let mut metadata = loki();
+        metadata.base = "u loki".to_string();
+        assert!(metadata.validate().is_err());
+        metadata.base = "u".repeat(MAX_UNIT_LEN);
+        assert!(metadata.validate().is_ok());
+        metadata.base.push('u');
+        assert!(metadata.validate().is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::address::Address;
+use crate::domain::models::token::decimal;
+use serde::{Deserialize, Serialize};
+
+/// Denom issued on chain by an account through the `token` app
+///
+/// The base unit is `factory/<creator>/<subdenom>`, so every creator has its own namespace
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactoryDenom {
+    /// base unit of the denom
+    pub denom: String,
+    pub creator: Address,
+    /// may mint and change the admin and the metadata; `None` once renounced
+    pub admin: Option<Address>,
+    /// tokens in circulation
+    #[serde(with = "decimal")]
+    pub supply: u128,
+    /// supply cap, minting never exceeds it
+    #[serde(with = "decimal")]
+    pub max_supply: u128
+}
+
+impl FactoryDenom {
+    /// Created with its creator as the admin and no supply
+    pub fn new(creator: &Address, subdenom: &str, max_supply: u128) -> Self {
+        FactoryDenom {
+            denom: format!("{}{}", Self::namespace(creator), subdenom),
+            creator: creator.clone(),
+            admin: Some(creator.clone()),
+            supply: 0,
+            max_supply
+        }
+    }
+
+    /// Prefix of the units of the denoms of `creator`; ex: `factory/lokichain1.../`
+    pub fn namespace(creator: &Address) -> String {
+        format!("factory/{}/", creator)
+    }
+
+    /// Supply is left unchanged if the cap would be exceeded
+    pub fn mint(&mut self, amount: u128) -> Result<(), DomainError> {
+        let supply = self.supply.checked_add(amount).ok_or(DomainError::Overflow)?;
+        if supply > self.max_supply {
+            return Err(DomainError::SupplyCapExceeded(format!("{}: at most {}", self.denom, self.max_supply)));
+        }
+        self.supply = supply;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, amount: u128) -> Result<(), DomainError> {
+        self.supply = self.supply.checked_sub(amount).ok_or(DomainError::Underflow)?;
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::signature::VerifyKey;
+
+    #[test]
+    fn test_supply() {
+        let creator = Address { network: "lokichain".to_string(), vk: VerifyKey::Ed25519([1; 32]) };
+        let mut denom = FactoryDenom::new(&creator, "points", 100);
+        assert_eq!(denom.denom, format!("factory/{}/points", creator));
+        assert_eq!(denom.admin, Some(creator));
+
+        denom.mint(60).unwrap();
+        assert!(matches!(denom.mint(41), Err(DomainError::SupplyCapExceeded(_))));
+        denom.mint(40).unwrap();
+        assert_eq!(denom.supply, 100);
+
+        denom.burn(30).unwrap();
+        assert_eq!(denom.burn(71), Err(DomainError::Underflow));
+        assert_eq!(denom.supply, 70);
+
+        denom.max_supply = u128::MAX;
+        assert_eq!(denom.mint(u128::MAX), Err(DomainError::Overflow));
+    }
+}
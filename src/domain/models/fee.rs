@@ -0,0 +1,54 @@
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::token::Token;
+use serde::{Deserialize, Serialize};
+
+/// Gas limit and price the sender agrees to pay for the execution
+///
+/// Up to `gas_limit * gas_price` is charged before the execution, the unused gas is refunded after it.
+/// The denom of `gas_price` is the fee denom
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fee {
+    /// maximum gas the execution may use
+    pub gas_limit: u64,
+    /// price of one unit of gas
+    pub gas_price: Token
+}
+
+impl Fee {
+    pub fn new(gas_limit: u64, gas_price: Token) -> Self {
+        Fee { gas_limit, gas_price }
+    }
+
+    /// Price of `gas` units
+    pub fn cost(&self, gas: u64) -> Result<Token, DomainError> {
+        self.gas_price.checked_mul(gas as u128)
+    }
+
+    /// Fee charged upfront for the whole gas limit
+    pub fn max_fee(&self) -> Result<Token, DomainError> {
+        self.cost(self.gas_limit)
+    }
+
+    /// Refund of the gas left after using `gas_used`
+    pub fn refund(&self, gas_used: u64) -> Result<Token, DomainError> {
+        self.cost(self.gas_limit.saturating_sub(gas_used))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee() {
+        let fee = Fee::new(1000, Token::new(25, "uusdt"));
+        assert_eq!(fee.max_fee().unwrap(), Token::new(25_000, "uusdt"));
+        assert_eq!(fee.cost(400).unwrap(), Token::new(10_000, "uusdt"));
+        assert_eq!(fee.refund(400).unwrap(), Token::new(15_000, "uusdt"));
+        assert_eq!(fee.refund(2000).unwrap(), Token::zero("uusdt"));
+
+        let fee = Fee::new(u64::MAX, Token::new(u128::MAX / 2, "LOKI"));
+        assert!(matches!(fee.max_fee(), Err(DomainError::Overflow)));
+    }
+}
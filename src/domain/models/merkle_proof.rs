@@ -0,0 +1,13 @@
+use crate::domain::models::hash::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Inclusion proof of a single leaf in a merkle tree (RFC 6962 audit path)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// position of the leaf, starting from 0
+    pub index: u64,
+    /// number of leaves in the tree
+    pub leaf_count: u64,
+    /// sibling hashes from the leaf level up to the root
+    pub siblings: Vec<Hash>
+}
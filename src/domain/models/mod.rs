@@ -6,3 +6,11 @@ pub mod hash;
 pub mod signature;
 pub mod app_data;
 pub mod account;
+pub mod merkle_proof;
+pub mod state_proof;
+pub mod denom;
+pub mod fee;
+pub mod validity;
+pub mod factory_denom;
+pub mod allowance;
+pub mod receipt;
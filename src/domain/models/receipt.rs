@@ -0,0 +1,76 @@
+use crate::domain::models::hash::Hash;
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::TxState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Event emitted by an app operation; ex: `bank` `transfer` with `sender`, `receiver` and `amount`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub app: String,
+    pub kind: String,
+    pub attributes: BTreeMap<String, String>
+}
+
+impl Event {
+    pub fn new(app: &str, kind: &str) -> Self {
+        Event { app: app.to_string(), kind: kind.to_string(), attributes: BTreeMap::new() }
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Why the operation of a transaction was reverted
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevertReason {
+    pub code: u32,
+    pub message: String
+}
+
+/// Outcome of a transaction executed in a block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Receipt {
+    pub tx_hash: Hash,
+    pub block_height: u64,
+    /// known only once the block is sealed, the block commits to the receipts without it
+    pub block_hash: Hash,
+    /// position of the transaction in the block
+    pub index: u32,
+    /// `Confirmed` or `Reverted`
+    pub state: TxState,
+    pub gas_used: u64,
+    /// fee paid by the sender after the refund
+    pub fee: Token,
+    /// set when the state is `Reverted`
+    pub error: Option<RevertReason>,
+    /// events of the operation, none if it was reverted
+    pub events: Vec<Event>
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_receipt() {
+        let receipt = Receipt {
+            tx_hash: Hash([1; 32]),
+            block_height: 7,
+            block_hash: Hash([2; 32]),
+            index: 3,
+            state: TxState::Confirmed,
+            gas_used: 21,
+            fee: Token::new(21, "LOKI"),
+            error: None,
+            events: vec![Event::new("bank", "transfer").with("amount", 10)]
+        };
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["events"][0]["attributes"]["amount"], "10");
+        assert_eq!(serde_json::from_value::<Receipt>(json).unwrap(), receipt);
+    }
+}
@@ -1,17 +1,177 @@
 use base64::engine::general_purpose::STANDARD_NO_PAD;
 use base64::Engine;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use zeroize::Zeroize;
+use crate::domain::exceptions::DomainError;
 
-/// Signature 64 bytes
+/// Signature algorithm of keys and signatures
+///
+/// In byte form (serde, address, encoding) Ed25519 values are raw bytes, as they were before
+/// other schemes existed. Values of other schemes are prefixed with the scheme `[tag]` byte
+#[derive(Clone, Copy, Debug, Eq, PartialEq, core::hash::Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    Ed25519,
+    Secp256k1,
+    /// m-of-n threshold of other schemes
+    Multisig,
+}
+
+impl SignatureScheme {
+    pub fn tag(&self) -> u8 {
+        match self {
+            SignatureScheme::Ed25519 => 0x00,
+            SignatureScheme::Secp256k1 => 0x01,
+            SignatureScheme::Multisig => 0x02,
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0x00 => Some(SignatureScheme::Ed25519),
+            0x01 => Some(SignatureScheme::Secp256k1),
+            0x02 => Some(SignatureScheme::Multisig),
+            _ => None,
+        }
+    }
+}
+
+fn to_tagged_bytes(scheme: SignatureScheme, bytes: &[u8]) -> Vec<u8> {
+    match scheme {
+        SignatureScheme::Ed25519 => bytes.to_vec(),
+        _ => [&[scheme.tag()], bytes].concat(),
+    }
+}
+
+/// Splits tagged bytes into the scheme and the raw value
+fn from_tagged_bytes<'a>(
+    name: &str,
+    bytes: &'a [u8],
+    ed25519_len: usize
+) -> Result<(SignatureScheme, &'a [u8]), DomainError> {
+    if bytes.len() == ed25519_len {
+        return Ok((SignatureScheme::Ed25519, bytes));
+    }
+    match bytes.split_first() {
+        Some((tag, value)) => match SignatureScheme::from_tag(*tag) {
+            Some(SignatureScheme::Ed25519) | None => {
+                Err(DomainError::InvalidKey(format!("{} has unknown scheme tag {}", name, tag)))
+            }
+            Some(scheme) => Ok((scheme, value)),
+        },
+        None => Err(DomainError::InvalidKey(format!("{} is empty", name))),
+    }
+}
+
+fn to_array<const N: usize>(name: &str, scheme: SignatureScheme, bytes: &[u8]) -> Result<[u8; N], DomainError> {
+    <[u8; N]>::try_from(bytes).map_err(
+        |_| DomainError::InvalidKey(format!("{:?} {} must be {} bytes", scheme, name, N))
+    )
+}
+
+/// Reads a `u8` length prefixed value from the front of `bytes`
+fn split_value<'a>(name: &str, bytes: &mut &'a [u8]) -> Result<&'a [u8], DomainError> {
+    let invalid = || DomainError::InvalidKey(format!("{} is truncated", name));
+    let (len, rest) = bytes.split_first().ok_or_else(invalid)?;
+    if rest.len() < *len as usize {
+        return Err(invalid());
+    }
+    let (value, rest) = rest.split_at(*len as usize);
+    *bytes = rest;
+    Ok(value)
+}
+
+fn split_byte(name: &str, bytes: &mut &[u8]) -> Result<u8, DomainError> {
+    let (byte, rest) = bytes.split_first()
+        .ok_or_else(|| DomainError::InvalidKey(format!("{} is truncated", name)))?;
+    *bytes = rest;
+    Ok(*byte)
+}
+
+fn push_value(buf: &mut Vec<u8>, value: &[u8]) {
+    buf.push(u8::try_from(value.len()).expect("single key values are shorter than 256 bytes"));
+    buf.extend_from_slice(value);
+}
+
+fn deserialize_bytes<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    STANDARD_NO_PAD.decode(&s).map_err(serde::de::Error::custom)
+}
+
+
+/// Signature
+///
+/// - Ed25519: 64 bytes
+/// - Secp256k1: 65 bytes, compact `r || s` followed by the recovery id
+/// - Multisig: signatures of the `[MultisigKey]` members with the index of the key,
+///   in byte form a count followed by the index and the `u8` length prefixed signature
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Signature(pub [u8; 64]);
+pub enum Signature {
+    Ed25519([u8; 64]),
+    Secp256k1([u8; 65]),
+    Multisig(Vec<(u8, Signature)>),
+}
+
+impl Signature {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            Signature::Ed25519(_) => SignatureScheme::Ed25519,
+            Signature::Secp256k1(_) => SignatureScheme::Secp256k1,
+            Signature::Multisig(_) => SignatureScheme::Multisig,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Signature::Ed25519(bytes) => to_tagged_bytes(self.scheme(), bytes),
+            Signature::Secp256k1(bytes) => to_tagged_bytes(self.scheme(), bytes),
+            Signature::Multisig(signatures) => {
+                let count = u8::try_from(signatures.len()).expect("multisig has at most 255 signatures");
+                let mut buf = vec![self.scheme().tag(), count];
+                for (index, signature) in signatures {
+                    buf.push(*index);
+                    push_value(&mut buf, &signature.to_bytes());
+                }
+                buf
+            }
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DomainError> {
+        match from_tagged_bytes("Signature", bytes, 64)? {
+            (scheme @ SignatureScheme::Ed25519, value) => Ok(Signature::Ed25519(to_array("Signature", scheme, value)?)),
+            (scheme @ SignatureScheme::Secp256k1, value) => Ok(Signature::Secp256k1(to_array("Signature", scheme, value)?)),
+            (SignatureScheme::Multisig, mut value) => {
+                let count = split_byte("Signature", &mut value)?;
+                let mut signatures = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    let index = split_byte("Signature", &mut value)?;
+                    let signature = Signature::from_bytes(split_value("Signature", &mut value)?)?;
+                    if signature.scheme() == SignatureScheme::Multisig {
+                        return Err(DomainError::InvalidKey("multisig Signature can not be nested".to_string()));
+                    }
+                    signatures.push((index, signature));
+                }
+                if !value.is_empty() {
+                    return Err(DomainError::InvalidKey("multisig Signature has trailing bytes".to_string()));
+                }
+                Ok(Signature::Multisig(signatures))
+            }
+        }
+    }
+}
 
 impl Serialize for Signature {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&STANDARD_NO_PAD.encode(&self.0))
+        serializer.serialize_str(&STANDARD_NO_PAD.encode(self.to_bytes()))
     }
 }
 
@@ -20,27 +180,82 @@ impl<'de> Deserialize<'de> for Signature {
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let bytes = STANDARD_NO_PAD.decode(&s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != 64 {
-            return Err(serde::de::Error::custom("Signature must be 64 bytes"));
+        Signature::from_bytes(&deserialize_bytes(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Order of the secp256k1 group, big endian
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Secret scalar of a secp256k1 `[SignKey]`, always non-zero and below the curve order
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Secp256k1Scalar([u8; 32]);
+
+impl Secp256k1Scalar {
+    pub fn new(scalar: [u8; 32]) -> Result<Self, DomainError> {
+        if scalar == [0; 32] || scalar >= SECP256K1_ORDER {
+            return Err(DomainError::InvalidKey("Secp256k1 SignKey must be a non-zero scalar below the curve order".to_string()));
         }
-        let mut array = [0u8; 64];
-        array.copy_from_slice(&bytes);
-        Ok(Signature(array))
+        Ok(Secp256k1Scalar(scalar))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
     }
 }
 
-/// SignKey 32 bytes
+/// SignKey
+///
+/// - Ed25519: 32 bytes secret seed
+/// - Secp256k1: 32 bytes secret scalar
+///
+/// A zeroized key must not be used again
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct SignKey(pub [u8; 32]);
+pub enum SignKey {
+    Ed25519([u8; 32]),
+    Secp256k1(Secp256k1Scalar),
+}
+
+impl SignKey {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            SignKey::Ed25519(_) => SignatureScheme::Ed25519,
+            SignKey::Secp256k1(_) => SignatureScheme::Secp256k1,
+        }
+    }
+
+    /// Raw key without the scheme tag
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            SignKey::Ed25519(bytes) => bytes,
+            SignKey::Secp256k1(scalar) => scalar.as_bytes(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_tagged_bytes(self.scheme(), self.as_bytes())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DomainError> {
+        match from_tagged_bytes("SignKey", bytes, 32)? {
+            (scheme @ SignatureScheme::Ed25519, value) => Ok(SignKey::Ed25519(to_array("SignKey", scheme, value)?)),
+            (scheme @ SignatureScheme::Secp256k1, value) => {
+                Ok(SignKey::Secp256k1(Secp256k1Scalar::new(to_array("SignKey", scheme, value)?)?))
+            }
+            (SignatureScheme::Multisig, _) => Err(DomainError::InvalidKey("multisig has no SignKey".to_string())),
+        }
+    }
+}
 
 impl Serialize for SignKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&STANDARD_NO_PAD.encode(&self.0))
+        serializer.serialize_str(&STANDARD_NO_PAD.encode(self.to_bytes()))
     }
 }
 
@@ -49,28 +264,118 @@ impl<'de> Deserialize<'de> for SignKey {
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let bytes = STANDARD_NO_PAD.decode(&s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != 32 {
-            return Err(serde::de::Error::custom("SignKey must be 32 bytes"));
+        SignKey::from_bytes(&deserialize_bytes(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Zeroize for SignKey {
+    fn zeroize(&mut self) {
+        match self {
+            SignKey::Ed25519(bytes) => bytes.zeroize(),
+            SignKey::Secp256k1(scalar) => scalar.0.zeroize(),
         }
-        let mut array = [0u8; 32];
-        array.copy_from_slice(&bytes);
-        Ok(SignKey(array))
     }
 }
 
+/// `[SignKey]` that is wiped from memory on drop
+///
+/// Can not be serialized and is hidden in `Debug` output
+pub struct SecretSignKey(SignKey);
+
+impl SecretSignKey {
+    pub fn new(sign_key: SignKey) -> Self {
+        SecretSignKey(sign_key)
+    }
+}
+
+impl Deref for SecretSignKey {
+    type Target = SignKey;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Debug for SecretSignKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretSignKey(***)")
+    }
+}
 
-/// VerifyKey 32 bytes
+impl Drop for SecretSignKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+
+/// VerifyKey
+///
+/// - Ed25519: 32 bytes compressed point
+/// - Secp256k1: 33 bytes SEC1 compressed point
+/// - Multisig: `[MultisigKey]`, the address of a multisig account is derived from all of its keys
 #[derive(Clone, Debug, Eq, PartialEq, core::hash::Hash)]
-pub struct VerifyKey(pub [u8; 32]);
+pub enum VerifyKey {
+    Ed25519([u8; 32]),
+    Secp256k1([u8; 33]),
+    Multisig(MultisigKey),
+}
+
+impl VerifyKey {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            VerifyKey::Ed25519(_) => SignatureScheme::Ed25519,
+            VerifyKey::Secp256k1(_) => SignatureScheme::Secp256k1,
+            VerifyKey::Multisig(_) => SignatureScheme::Multisig,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            VerifyKey::Ed25519(bytes) => to_tagged_bytes(self.scheme(), bytes),
+            VerifyKey::Secp256k1(bytes) => to_tagged_bytes(self.scheme(), bytes),
+            VerifyKey::Multisig(key) => {
+                let mut buf = vec![self.scheme().tag(), key.threshold, key.keys.len() as u8];
+                for verify_key in &key.keys {
+                    push_value(&mut buf, &verify_key.to_bytes());
+                }
+                buf
+            }
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DomainError> {
+        match from_tagged_bytes("VerifyKey", bytes, 32)? {
+            (scheme @ SignatureScheme::Ed25519, value) => Ok(VerifyKey::Ed25519(to_array("VerifyKey", scheme, value)?)),
+            (scheme @ SignatureScheme::Secp256k1, value) => Ok(VerifyKey::Secp256k1(to_array("VerifyKey", scheme, value)?)),
+            (SignatureScheme::Multisig, mut value) => {
+                let threshold = split_byte("VerifyKey", &mut value)?;
+                let count = split_byte("VerifyKey", &mut value)?;
+                let mut keys = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    keys.push(VerifyKey::from_bytes(split_value("VerifyKey", &mut value)?)?);
+                }
+                if !value.is_empty() {
+                    return Err(DomainError::InvalidKey("multisig VerifyKey has trailing bytes".to_string()));
+                }
+
+                let key = MultisigKey::new(threshold, keys)?;
+                // byte form must be canonical, otherwise one account would have several addresses
+                if key.to_verify_key().to_bytes() != bytes {
+                    return Err(DomainError::InvalidKey("multisig VerifyKey keys must be sorted".to_string()));
+                }
+                Ok(VerifyKey::Multisig(key))
+            }
+        }
+    }
+}
 
 impl Serialize for VerifyKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&STANDARD_NO_PAD.encode(&self.0))
+        serializer.serialize_str(&STANDARD_NO_PAD.encode(self.to_bytes()))
     }
 }
 
@@ -79,14 +384,57 @@ impl<'de> Deserialize<'de> for VerifyKey {
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let bytes = STANDARD_NO_PAD.decode(&s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != 32 {
-            return Err(serde::de::Error::custom("VerifyKey must be 32 bytes"));
+        VerifyKey::from_bytes(&deserialize_bytes(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// m-of-n multisig key
+///
+/// At least `threshold` of the `keys` must sign. Keys are kept sorted by their byte form,
+/// so the same set of keys and threshold always gives the same address
+#[derive(Clone, Debug, Eq, PartialEq, core::hash::Hash)]
+pub struct MultisigKey {
+    threshold: u8,
+    keys: Vec<VerifyKey>
+}
+
+impl MultisigKey {
+    pub const MAX_KEYS: usize = 16;
+
+    pub fn new(threshold: u8, mut keys: Vec<VerifyKey>) -> Result<Self, DomainError> {
+        if keys.is_empty() || keys.len() > Self::MAX_KEYS {
+            return Err(DomainError::InvalidKey(format!("multisig must have 1 to {} keys", Self::MAX_KEYS)));
         }
-        let mut array = [0u8; 32];
-        array.copy_from_slice(&bytes);
-        Ok(VerifyKey(array))
+        if threshold == 0 || threshold as usize > keys.len() {
+            return Err(DomainError::InvalidKey(format!("multisig threshold must be 1 to {}", keys.len())));
+        }
+        if keys.iter().any(|key| key.scheme() == SignatureScheme::Multisig) {
+            return Err(DomainError::InvalidKey("multisig can not be nested".to_string()));
+        }
+
+        keys.sort_by_cached_key(|key| key.to_bytes());
+        if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(DomainError::InvalidKey("multisig keys must be unique".to_string()));
+        }
+        Ok(MultisigKey { threshold, keys })
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Member keys in sorted order, the index of a signature refers to this order
+    pub fn keys(&self) -> &[VerifyKey] {
+        &self.keys
+    }
+
+    /// Position of the member key, used as the index of its signature
+    pub fn index_of(&self, verify_key: &VerifyKey) -> Option<u8> {
+        self.keys.iter().position(|key| key == verify_key).map(|index| index as u8)
+    }
+
+    pub fn to_verify_key(&self) -> VerifyKey {
+        VerifyKey::Multisig(self.clone())
     }
 }
 
@@ -97,37 +445,151 @@ mod tests {
 
     #[test]
     fn serialize_deserialize_signature() {
-        let original_signature = Signature([(); 64].map(|_| random()));
+        let original_signature = Signature::Ed25519([(); 64].map(|_| random()));
 
         let serialized = serde_json::to_string(&original_signature).unwrap();
         let deserialized_signature: Signature = serde_json::from_str(&serialized).unwrap();
         assert_eq!(original_signature, deserialized_signature);
 
-        let new_signature = Signature([(); 64].map(|_| random()));
-        assert_ne!(new_signature, deserialized_signature)
+        let new_signature = Signature::Ed25519([(); 64].map(|_| random()));
+        assert_ne!(new_signature, deserialized_signature);
+
+        let secp256k1_signature = Signature::Secp256k1([(); 65].map(|_| random()));
+        let serialized = serde_json::to_string(&secp256k1_signature).unwrap();
+        assert_eq!(serde_json::from_str::<Signature>(&serialized).unwrap(), secp256k1_signature);
+    }
+
+    #[test]
+    fn secp256k1_signkey_scalar_range() {
+        let tagged = |scalar: [u8; 32]| [&[SignatureScheme::Secp256k1.tag()], scalar.as_slice()].concat();
+        for invalid in [[0; 32], SECP256K1_ORDER, [0xff; 32]] {
+            assert!(Secp256k1Scalar::new(invalid).is_err());
+            assert!(SignKey::from_bytes(&tagged(invalid)).is_err());
+        }
+
+        let mut largest = SECP256K1_ORDER;
+        largest[31] -= 1;
+        let sign_key = SignKey::Secp256k1(Secp256k1Scalar::new(largest).unwrap());
+        assert_eq!(SignKey::from_bytes(&tagged(largest)).unwrap(), sign_key);
+        assert_eq!(sign_key.to_bytes(), tagged(largest));
+        assert!(SignKey::from_bytes(&tagged([7; 32])).is_ok());
     }
 
     #[test]
     fn serialize_deserialize_signkey() {
-        let original_signkey = SignKey([(); 32].map(|_| random()));
+        let original_signkey = SignKey::Ed25519([(); 32].map(|_| random()));
 
         let serialized = serde_json::to_string(&original_signkey).unwrap();
         let deserialized_signkey: SignKey = serde_json::from_str(&serialized).unwrap();
         assert_eq!(original_signkey, deserialized_signkey);
 
-        let new_signkey = SignKey([(); 32].map(|_| random()));
-        assert_ne!(new_signkey, deserialized_signkey)
+        let new_signkey = SignKey::Ed25519([(); 32].map(|_| random()));
+        assert_ne!(new_signkey, deserialized_signkey);
+
+        let secp256k1_signkey = SignKey::Secp256k1(Secp256k1Scalar::new(original_signkey.as_bytes().try_into().unwrap()).unwrap());
+        let serialized = serde_json::to_string(&secp256k1_signkey).unwrap();
+        assert_eq!(serde_json::from_str::<SignKey>(&serialized).unwrap(), secp256k1_signkey);
+        assert_ne!(secp256k1_signkey, original_signkey);
+    }
+
+    #[test]
+    fn secret_signkey() {
+        let mut sign_key = SignKey::Ed25519([7; 32]);
+        let secret = SecretSignKey::new(sign_key.clone());
+        assert_eq!(*secret, sign_key);
+        assert_eq!(format!("{:?}", secret), "SecretSignKey(***)");
+
+        sign_key.zeroize();
+        assert_eq!(sign_key, SignKey::Ed25519([0; 32]));
     }
 
     #[test]
     fn serialize_deserialize_verifykey() {
-        let original_verifykey = VerifyKey([(); 32].map(|_| random()));
+        let original_verifykey = VerifyKey::Ed25519([(); 32].map(|_| random()));
 
         let serialized = serde_json::to_string(&original_verifykey).unwrap();
         let deserialized_verifykey: VerifyKey = serde_json::from_str(&serialized).unwrap();
         assert_eq!(original_verifykey, deserialized_verifykey);
 
-        let new_verifykey = VerifyKey([(); 32].map(|_| random()));
-        assert_ne!(new_verifykey, deserialized_verifykey)
+        let new_verifykey = VerifyKey::Ed25519([(); 32].map(|_| random()));
+        assert_ne!(new_verifykey, deserialized_verifykey);
+
+        let secp256k1_verifykey = VerifyKey::Secp256k1([(); 33].map(|_| random()));
+        let serialized = serde_json::to_string(&secp256k1_verifykey).unwrap();
+        assert_eq!(serde_json::from_str::<VerifyKey>(&serialized).unwrap(), secp256k1_verifykey);
+    }
+
+    #[test]
+    fn legacy_ed25519_bytes() {
+        // values serialized before scheme tags existed are raw Ed25519 bytes
+        let legacy = format!("\"{}\"", STANDARD_NO_PAD.encode([5u8; 32]));
+        assert_eq!(serde_json::from_str::<VerifyKey>(&legacy).unwrap(), VerifyKey::Ed25519([5; 32]));
+        assert_eq!(VerifyKey::Ed25519([5; 32]).to_bytes(), vec![5u8; 32]);
+
+        let mut secp256k1 = vec![SignatureScheme::Secp256k1.tag()];
+        secp256k1.extend_from_slice(&[2u8; 33]);
+        assert_eq!(VerifyKey::from_bytes(&secp256k1).unwrap(), VerifyKey::Secp256k1([2; 33]));
+        assert_eq!(VerifyKey::Secp256k1([2; 33]).to_bytes(), secp256k1);
+    }
+
+    #[test]
+    fn invalid_tagged_bytes() {
+        assert!(VerifyKey::from_bytes(&[]).is_err());
+        assert!(VerifyKey::from_bytes(&[0x01; 33]).is_err());
+        assert!(VerifyKey::from_bytes(&[0x00; 33]).is_err());
+        assert!(VerifyKey::from_bytes(&[0x7f; 34]).is_err());
+        assert!(Signature::from_bytes(&[0x01; 65]).is_err());
+        assert!(SignKey::from_bytes(&[0x01; 32]).is_ok());
+        assert!(SignKey::from_bytes(&[0x02; 33]).is_err());
+    }
+
+    #[test]
+    fn multisig_key() {
+        let keys = vec![VerifyKey::Secp256k1([2; 33]), VerifyKey::Ed25519([3; 32]), VerifyKey::Ed25519([1; 32])];
+        let key = MultisigKey::new(2, keys.clone()).unwrap();
+        assert_eq!(key.threshold(), 2);
+        assert_eq!(key.keys()[0], VerifyKey::Ed25519([1; 32]));
+        assert_eq!(key.index_of(&VerifyKey::Secp256k1([2; 33])), Some(1));
+
+        let verify_key = key.to_verify_key();
+        assert_eq!(VerifyKey::from_bytes(&verify_key.to_bytes()).unwrap(), verify_key);
+        let serialized = serde_json::to_string(&verify_key).unwrap();
+        assert_eq!(serde_json::from_str::<VerifyKey>(&serialized).unwrap(), verify_key);
+
+        assert!(MultisigKey::new(0, keys.clone()).is_err());
+        assert!(MultisigKey::new(4, keys.clone()).is_err());
+        assert!(MultisigKey::new(1, vec![]).is_err());
+        assert!(MultisigKey::new(1, vec![VerifyKey::Ed25519([1; 32]); 2]).is_err());
+        assert!(MultisigKey::new(1, vec![verify_key.clone()]).is_err());
+        assert!(MultisigKey::new(1, (0..17).map(|i| VerifyKey::Ed25519([i; 32])).collect()).is_err());
+
+        // unsorted keys and trailing bytes are not canonical
+        let mut unsorted = vec![SignatureScheme::Multisig.tag(), 1, 2, 32];
+        unsorted.extend_from_slice(&[3; 32]);
+        unsorted.push(32);
+        unsorted.extend_from_slice(&[1; 32]);
+        assert!(VerifyKey::from_bytes(&unsorted).is_err());
+        let mut trailing = verify_key.to_bytes();
+        trailing.push(0);
+        assert!(VerifyKey::from_bytes(&trailing).is_err());
+        assert!(VerifyKey::from_bytes(&verify_key.to_bytes()[..40]).is_err());
+    }
+
+    #[test]
+    fn multisig_signature() {
+        let signature = Signature::Multisig(vec![
+            (0, Signature::Ed25519([1; 64])),
+            (2, Signature::Secp256k1([2; 65])),
+        ]);
+        assert_eq!(Signature::from_bytes(&signature.to_bytes()).unwrap(), signature);
+        let serialized = serde_json::to_string(&signature).unwrap();
+        assert_eq!(serde_json::from_str::<Signature>(&serialized).unwrap(), signature);
+
+        let nested = Signature::Multisig(vec![(0, signature.clone())]);
+        assert!(Signature::from_bytes(&nested.to_bytes()).is_err());
+
+        let bytes = signature.to_bytes();
+        assert!(Signature::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Signature::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
     }
 }
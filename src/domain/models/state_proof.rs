@@ -0,0 +1,20 @@
+use crate::domain::models::hash::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Leaf of the state tree: an account, an app state entry or a denom
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateLeaf {
+    /// hash of the namespaced key, the path of the leaf
+    pub key: Hash,
+    /// hash of the namespaced value
+    pub value_hash: Hash
+}
+
+/// Membership or non-membership proof of a key in the state tree
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProof {
+    /// leaf found on the path of the key: the key itself, another key, or none
+    pub leaf: Option<StateLeaf>,
+    /// sibling hashes from the root down to the leaf
+    pub siblings: Vec<Hash>
+}
@@ -1,27 +1,128 @@
 use std::cmp::Ordering;
 use serde::{Deserialize, Serialize};
+use crate::domain::exceptions::DomainError;
 
+/// Amount of a single denomination
+///
+/// Arithmetic and ordering are only defined for tokens of the same denom,
+/// use the `checked_*` methods; they never overflow or panic
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Token {
-    pub value: u64,
+    pub value: u128,
     pub denom: String
 }
 
-impl PartialOrd for Token {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+impl Token {
+    pub fn new(value: u128, denom: &str) -> Self {
+        Token { value, denom: denom.to_string() }
+    }
+
+    pub fn zero(denom: &str) -> Self {
+        Token::new(0, denom)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    fn check_denom(&self, other: &Token) -> Result<(), DomainError> {
         if self.denom != other.denom {
-            return None;
+            return Err(DomainError::DenomMismatch(self.denom.clone(), other.denom.clone()));
         }
-        Some(self.value.cmp(&other.value))
+        Ok(())
+    }
+
+    pub fn checked_cmp(&self, other: &Token) -> Result<Ordering, DomainError> {
+        self.check_denom(other)?;
+        Ok(self.value.cmp(&other.value))
+    }
+
+    pub fn checked_add(&self, other: &Token) -> Result<Token, DomainError> {
+        self.check_denom(other)?;
+        let value = self.value.checked_add(other.value).ok_or(DomainError::Overflow)?;
+        Ok(Token { value, denom: self.denom.clone() })
+    }
+
+    pub fn checked_sub(&self, other: &Token) -> Result<Token, DomainError> {
+        self.check_denom(other)?;
+        let value = self.value.checked_sub(other.value).ok_or(DomainError::Underflow)?;
+        Ok(Token { value, denom: self.denom.clone() })
+    }
+
+    pub fn checked_mul(&self, factor: u128) -> Result<Token, DomainError> {
+        let value = self.value.checked_mul(factor).ok_or(DomainError::Overflow)?;
+        Ok(Token { value, denom: self.denom.clone() })
+    }
+
+    /// Integer division, rounded down
+    pub fn checked_div(&self, divisor: u128) -> Result<Token, DomainError> {
+        let value = self.value.checked_div(divisor).ok_or(DomainError::DivisionByZero)?;
+        Ok(Token { value, denom: self.denom.clone() })
     }
 }
 
-impl Ord for Token {
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.denom != other.denom {
-            panic!("Cannot compare tokens with different denominations");
+/// Tokens of different denoms are not comparable, every comparison operator returns `false`
+impl PartialOrd for Token {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.checked_cmp(other).ok()
+    }
+}
+
+/// Serde of a `u128` amount as a decimal string, integers up to `u64::MAX` are read as well
+///
+/// JSON `[serde_json::Value]`s, such as app payloads, hold no integer above `u64::MAX`
+pub mod decimal {
+    use serde::{de, Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = u128;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an amount as a string of decimal digits")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<u128, E> {
+                Ok(value.into())
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<u128, E> {
+                let digits = !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+                digits.then(|| value.parse().ok()).flatten()
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
         }
-        self.value.cmp(&other.value)
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Serde of a `[Token]` with its value as a decimal string, see `[decimal]`
+pub mod decimal_token {
+    use super::Token;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct DecimalToken {
+        #[serde(with = "super::decimal")]
+        value: u128,
+        denom: String
+    }
+
+    pub fn serialize<S: Serializer>(token: &Token, serializer: S) -> Result<S::Ok, S::Error> {
+        DecimalToken { value: token.value, denom: token.denom.clone() }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Token, D::Error> {
+        let token = DecimalToken::deserialize(deserializer)?;
+        Ok(Token { value: token.value, denom: token.denom })
     }
 }
 
@@ -29,6 +130,31 @@ impl Ord for Token {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::Value;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        #[serde(with = "decimal_token")]
+        amount: Token
+    }
+
+    #[test]
+    fn test_decimal_serde() {
+        let payload = Payload { amount: Token::new(u128::MAX, "LOKI") };
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["amount"]["value"], Value::String(u128::MAX.to_string()));
+        assert_eq!(serde_json::from_value::<Payload>(value).unwrap(), payload);
+        assert_eq!(serde_json::from_str::<Payload>(&serde_json::to_string(&payload).unwrap()).unwrap(), payload);
+
+        let amount = |value: Value| serde_json::from_value::<Payload>(serde_json::json!({ "amount": { "value": value, "denom": "LOKI" } }))
+            .map(|payload| payload.amount.value);
+        assert_eq!(amount(Value::from(u64::MAX)).unwrap(), u64::MAX as u128);
+        assert!(amount(Value::from("340282366920938463463374607431768211456")).is_err());
+        assert!(amount(Value::from("+1")).is_err());
+        assert!(amount(Value::from("")).is_err());
+        assert!(amount(Value::from(1.5)).is_err());
+        assert!(amount(Value::from(-1)).is_err());
+    }
 
     #[test]
     fn test_equality() {
@@ -83,18 +209,45 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Cannot compare tokens with different denominations")]
-    fn test_ord_panic() {
-        let token1 = Token {
-            value: 100,
-            denom: "LOKI".to_string(),
-        };
+    fn test_checked_cmp() {
+        let token1 = Token::new(100, "LOKI");
+        let token2 = Token::new(200, "USDT");
+
+        assert_eq!(token1.checked_cmp(&Token::new(200, "LOKI")), Ok(Ordering::Less));
+        assert_eq!(token1.checked_cmp(&token1), Ok(Ordering::Equal));
+        assert_eq!(
+            token1.checked_cmp(&token2),
+            Err(DomainError::DenomMismatch("LOKI".to_string(), "USDT".to_string()))
+        );
+
+        // sorting mixed denoms does not panic
+        let mut tokens = vec![token2.clone(), token1.clone(), Token::new(50, "LOKI")];
+        tokens.sort_by(|a, b| (&a.denom, a.value).cmp(&(&b.denom, b.value)));
+        assert_eq!(tokens, vec![Token::new(50, "LOKI"), token1, token2]);
+    }
 
-        let token2 = Token {
-            value: 200,
-            denom: "USDT".to_string(),
-        };
+    #[test]
+    fn test_checked_arithmetic() {
+        let token = Token::new(100, "LOKI");
+
+        assert_eq!(token.checked_add(&Token::new(50, "LOKI")), Ok(Token::new(150, "LOKI")));
+        assert_eq!(token.checked_sub(&Token::new(100, "LOKI")), Ok(Token::zero("LOKI")));
+        assert_eq!(token.checked_mul(3), Ok(Token::new(300, "LOKI")));
+        assert_eq!(token.checked_div(3), Ok(Token::new(33, "LOKI")));
+
+        assert_eq!(token.checked_sub(&Token::new(101, "LOKI")), Err(DomainError::Underflow));
+        assert_eq!(Token::new(u128::MAX, "LOKI").checked_add(&Token::new(1, "LOKI")), Err(DomainError::Overflow));
+        assert_eq!(Token::new(u128::MAX, "LOKI").checked_mul(2), Err(DomainError::Overflow));
+        assert_eq!(token.checked_div(0), Err(DomainError::DivisionByZero));
+        assert!(matches!(token.checked_add(&Token::new(1, "USDT")), Err(DomainError::DenomMismatch(..))));
+        assert!(matches!(token.checked_sub(&Token::new(1, "USDT")), Err(DomainError::DenomMismatch(..))));
+    }
 
-        let _ = token1.cmp(&token2);
+    #[test]
+    fn test_high_supply() {
+        let supply = Token::new(u64::MAX as u128 * 1_000, "WEI");
+        let serialized = serde_json::to_string(&supply).unwrap();
+        assert_eq!(serialized, r#"{"value":18446744073709551615000,"denom":"WEI"}"#);
+        assert_eq!(serde_json::from_str::<Token>(&serialized).unwrap(), supply);
     }
 }
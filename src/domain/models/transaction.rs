@@ -1,16 +1,42 @@
-use chrono::Utc;
 use crate::domain::models::address::Address;
 use crate::domain::models::hash::Hash;
 use crate::domain::models::signature::Signature;
 use crate::domain::models::token::Token;
 use serde::{Deserialize, Serialize};
 use crate::domain::models::app_data::AppData;
+use crate::domain::models::fee::Fee;
+use crate::domain::models::validity::{ValidityBound, ValidityWindow};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum TxState {
     Confirmed,           // Транзакция подтверждена
     Reverted,            // Транзакция отклонена
     PendingConfirmation, // Ожидает подтверждения
+    Queued,              // Ожидает транзакции с меньшим nonce
+}
+
+/// Signed part of the transaction
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TxBody {
+    /// chain the transaction is signed for, so it can not be replayed on another chain
+    ///
+    /// `[Transaction]` does not keep it, every transaction of a chain has the chain id of its nodes
+    pub chain_id: String,
+    pub sender: Address,
+    pub data: AppData,
+    pub amount: Token,
+    pub fee: Fee,
+    pub nonce: u64,
+    #[serde(default)]
+    pub valid_after: Option<ValidityBound>,
+    #[serde(default)]
+    pub valid_until: Option<ValidityBound>
+}
+
+impl TxBody {
+    pub fn validity(&self) -> ValidityWindow {
+        ValidityWindow { valid_after: self.valid_after, valid_until: self.valid_until }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -20,18 +46,24 @@ pub struct Transaction {
     pub data: AppData,
     pub amount: Token,
     pub timestamp: u64,
-    pub gas: u64,
+    pub fee: Fee,
     pub nonce: u64,
+    #[serde(default)]
+    pub valid_after: Option<ValidityBound>,
+    #[serde(default)]
+    pub valid_until: Option<ValidityBound>,
     pub signature: Signature,
 }
 
 impl Transaction {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         hash: Hash,
         sender: Address,
         data: AppData,
         amount: Token,
-        gas: u64,
+        timestamp: u64,
+        fee: Fee,
         nonce: u64,
         signature: Signature
     ) -> Self {
@@ -40,12 +72,25 @@ impl Transaction {
             sender,
             data,
             amount,
-            timestamp: Utc::now().timestamp() as u64,
-            gas,
+            timestamp,
+            fee,
             nonce,
+            valid_after: None,
+            valid_until: None,
             signature
         }
     }
+
+    /// Sets the validity window signed in `[TxBody]`
+    pub fn with_validity(mut self, validity: ValidityWindow) -> Self {
+        self.valid_after = validity.valid_after;
+        self.valid_until = validity.valid_until;
+        self
+    }
+
+    pub fn validity(&self) -> ValidityWindow {
+        ValidityWindow { valid_after: self.valid_after, valid_until: self.valid_until }
+    }
 }
 
 
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Bound of a validity window, compared with the block a transaction is included in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidityBound {
+    /// block height
+    Height(u64),
+    /// block timestamp in seconds
+    Timestamp(u64),
+}
+
+impl ValidityBound {
+    /// (bound, position of the block) in the units of the bound
+    fn compare_with(&self, height: u64, timestamp: u64) -> (u64, u64) {
+        match self {
+            ValidityBound::Height(bound) => (*bound, height),
+            ValidityBound::Timestamp(bound) => (*bound, timestamp),
+        }
+    }
+}
+
+/// Blocks a transaction can be included in, both bounds are inclusive and optional
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidityWindow {
+    /// first block the transaction is valid in
+    pub valid_after: Option<ValidityBound>,
+    /// last block the transaction is valid in
+    pub valid_until: Option<ValidityBound>
+}
+
+impl ValidityWindow {
+    /// `false` if no block can ever be in the window
+    pub fn is_consistent(&self) -> bool {
+        match (self.valid_after, self.valid_until) {
+            (Some(ValidityBound::Height(after)), Some(ValidityBound::Height(until)))
+            | (Some(ValidityBound::Timestamp(after)), Some(ValidityBound::Timestamp(until))) => after <= until,
+            _ => true,
+        }
+    }
+
+    /// The block at `height` and `timestamp` is before the window
+    pub fn is_pending(&self, height: u64, timestamp: u64) -> bool {
+        self.valid_after.is_some_and(|bound| {
+            let (bound, position) = bound.compare_with(height, timestamp);
+            position < bound
+        })
+    }
+
+    /// The block at `height` and `timestamp` is past the window, so is every later block
+    pub fn is_expired(&self, height: u64, timestamp: u64) -> bool {
+        self.valid_until.is_some_and(|bound| {
+            let (bound, position) = bound.compare_with(height, timestamp);
+            position > bound
+        })
+    }
+
+    pub fn contains(&self, height: u64, timestamp: u64) -> bool {
+        !self.is_pending(height, timestamp) && !self.is_expired(height, timestamp)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window() {
+        let window = ValidityWindow {
+            valid_after: Some(ValidityBound::Height(10)),
+            valid_until: Some(ValidityBound::Timestamp(1000))
+        };
+        assert!(window.is_pending(9, 500));
+        assert!(window.contains(10, 500));
+        assert!(window.contains(20, 1000));
+        assert!(window.is_expired(20, 1001));
+        assert!(!window.contains(20, 1001));
+
+        assert!(ValidityWindow::default().contains(0, 0));
+        assert!(ValidityWindow::default().contains(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn test_consistency() {
+        let window = |after, until| ValidityWindow { valid_after: Some(after), valid_until: Some(until) };
+        assert!(window(ValidityBound::Height(5), ValidityBound::Height(5)).is_consistent());
+        assert!(!window(ValidityBound::Height(6), ValidityBound::Height(5)).is_consistent());
+        assert!(!window(ValidityBound::Timestamp(6), ValidityBound::Timestamp(5)).is_consistent());
+        // can not be compared before the block is known
+        assert!(window(ValidityBound::Timestamp(6), ValidityBound::Height(5)).is_consistent());
+    }
+
+    #[test]
+    fn test_serialize_bound() {
+        assert_eq!(serde_json::to_string(&ValidityBound::Height(7)).unwrap(), r#"{"height":7}"#);
+        assert_eq!(serde_json::from_str::<ValidityBound>(r#"{"timestamp":7}"#).unwrap(), ValidityBound::Timestamp(7));
+    }
+}
@@ -0,0 +1,442 @@
+//! Canonical binary encoding
+//!
+//! Deterministic byte representation used for hashing and signing.
+//! Every encoded message starts with a single `[ENCODING_VERSION]` byte, the rest is:
+//!
+//! - `u8` as 1 byte; `u32` as 4 bytes, `u64` as 8 bytes and `u128` as 16 bytes big-endian
+//! - `bytes` and `string` (UTF-8) as `u32` big-endian length followed by the bytes
+//! - `Hash` as raw bytes without a length
+//! - `Signature` as its scheme tagged bytes without a length, always the last field
+//! - `Address` as `string` network followed by `bytes` of the bech32 data part
+//! - `Token` as `string` denom followed by `u128` value
+//! - `Fee` as `u64` gas limit followed by the `Token` gas price
+//! - `Option` as `0x00` for none, or `0x01` followed by the value
+//! - `ValidityBound` as `0x00` for a height or `0x01` for a timestamp followed by `u64`
+//! - `Balances` as `u32` count followed by the non zero `Token`s sorted by denom bytes
+//! - `TxState` as a tag byte: `0x00` confirmed, `0x01` reverted, `0x02` pending confirmation, `0x03` queued
+//! - `Event` as `string` app, `string` kind and `u32` count followed by `string` key and value pairs sorted by key bytes
+//! - `DenomMetadata` as `string` base, `string` display, `u8` decimals and `string` symbol
+//! - `AppData` as `string` app, `string` operation and the `Value` payload
+//! - `Value` as a tag byte followed by:
+//!   - `0x00` null, `0x01` false, `0x02` true: nothing
+//!   - `0x03` unsigned integer: `u64`
+//!   - `0x04` negative integer: `i64` as 8 bytes big-endian two's complement
+//!   - `0x05` float: IEEE 754 bits as 8 bytes big-endian
+//!   - `0x06` string: `string`
+//!   - `0x07` array: `u32` count followed by the items
+//!   - `0x08` object: `u32` count followed by `string` key and value pairs sorted by key bytes
+//!
+//! Structures are encoded field by field in the order listed on their `[Encode]` impl.
+//! Golden vectors are published in `encoding_vectors.json` next to this file.
+
+use crate::domain::models::account::{Account, Balances};
+use crate::domain::models::address::Address;
+use crate::domain::models::app_data::AppData;
+use crate::domain::models::block::BlockHeader;
+use crate::domain::models::denom::DenomMetadata;
+use crate::domain::models::fee::Fee;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::receipt::{Event, Receipt, RevertReason};
+use crate::domain::models::signature::Signature;
+use crate::domain::models::token::Token;
+use crate::domain::models::transaction::{Transaction, TxBody, TxState};
+use crate::domain::models::validity::ValidityBound;
+use serde_json::Value;
+
+/// Version of the canonical encoding, first byte of every encoded message
+pub const ENCODING_VERSION: u8 = 6;
+
+pub trait Encode {
+    /// Appends the canonical representation of `self` to `buf`
+    fn encode_to(&self, buf: &mut Vec<u8>);
+}
+
+/// Encodes `value` as a versioned canonical message
+pub fn encode<T: Encode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut buf = vec![ENCODING_VERSION];
+    value.encode_to(&mut buf);
+    buf
+}
+
+fn encode_len(len: usize, buf: &mut Vec<u8>) {
+    let len = u32::try_from(len).expect("encoded length must fit into u32");
+    buf.extend_from_slice(&len.to_be_bytes());
+}
+
+impl Encode for u8 {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.push(*self);
+    }
+}
+
+impl Encode for u32 {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Encode for u64 {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Encode for u128 {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Encode for [u8] {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        encode_len(self.len(), buf);
+        buf.extend_from_slice(self);
+    }
+}
+
+impl Encode for str {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.as_bytes().encode_to(buf);
+    }
+}
+
+impl Encode for Hash {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0);
+    }
+}
+
+impl Encode for Signature {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_bytes());
+    }
+}
+
+impl Encode for Address {
+    /// network, bech32 data
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.network.encode_to(buf);
+        self.vk.to_bytes().encode_to(buf);
+    }
+}
+
+impl Encode for Token {
+    /// denom, value
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.denom.encode_to(buf);
+        self.value.encode_to(buf);
+    }
+}
+
+impl Encode for Fee {
+    /// gas_limit, gas_price
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.gas_limit.encode_to(buf);
+        self.gas_price.encode_to(buf);
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            None => buf.push(0x00),
+            Some(value) => {
+                buf.push(0x01);
+                value.encode_to(buf);
+            }
+        }
+    }
+}
+
+impl Encode for ValidityBound {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        let (tag, value) = match self {
+            ValidityBound::Height(height) => (0x00, height),
+            ValidityBound::Timestamp(timestamp) => (0x01, timestamp),
+        };
+        buf.push(tag);
+        value.encode_to(buf);
+    }
+}
+
+impl Encode for Value {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            Value::Null => buf.push(0x00),
+            Value::Bool(false) => buf.push(0x01),
+            Value::Bool(true) => buf.push(0x02),
+            Value::Number(number) => {
+                if let Some(value) = number.as_u64() {
+                    buf.push(0x03);
+                    value.encode_to(buf);
+                } else if let Some(value) = number.as_i64() {
+                    buf.push(0x04);
+                    buf.extend_from_slice(&value.to_be_bytes());
+                } else {
+                    let value = number.as_f64().expect("json number is u64, i64 or f64");
+                    buf.push(0x05);
+                    buf.extend_from_slice(&value.to_bits().to_be_bytes());
+                }
+            }
+            Value::String(value) => {
+                buf.push(0x06);
+                value.encode_to(buf);
+            }
+            Value::Array(items) => {
+                buf.push(0x07);
+                encode_len(items.len(), buf);
+                for item in items {
+                    item.encode_to(buf);
+                }
+            }
+            Value::Object(map) => {
+                buf.push(0x08);
+                encode_len(map.len(), buf);
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+                for (key, value) in entries {
+                    key.encode_to(buf);
+                    value.encode_to(buf);
+                }
+            }
+        }
+    }
+}
+
+impl Encode for AppData {
+    /// app, operation, payload
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.app.encode_to(buf);
+        self.operation.encode_to(buf);
+        self.payload.encode_to(buf);
+    }
+}
+
+impl Encode for TxBody {
+    /// chain_id, sender, data, amount, fee, nonce, valid_after, valid_until
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.chain_id.encode_to(buf);
+        self.sender.encode_to(buf);
+        self.data.encode_to(buf);
+        self.amount.encode_to(buf);
+        self.fee.encode_to(buf);
+        self.nonce.encode_to(buf);
+        self.valid_after.encode_to(buf);
+        self.valid_until.encode_to(buf);
+    }
+}
+
+impl Encode for Transaction {
+    /// hash, sender, data, amount, timestamp, fee, nonce, valid_after, valid_until, signature
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.hash.encode_to(buf);
+        self.sender.encode_to(buf);
+        self.data.encode_to(buf);
+        self.amount.encode_to(buf);
+        self.timestamp.encode_to(buf);
+        self.fee.encode_to(buf);
+        self.nonce.encode_to(buf);
+        self.valid_after.encode_to(buf);
+        self.valid_until.encode_to(buf);
+        self.signature.encode_to(buf);
+    }
+}
+
+impl Encode for TxState {
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        let tag: u8 = match self {
+            TxState::Confirmed => 0x00,
+            TxState::Reverted => 0x01,
+            TxState::PendingConfirmation => 0x02,
+            TxState::Queued => 0x03,
+        };
+        tag.encode_to(buf);
+    }
+}
+
+impl Encode for Event {
+    /// app, kind, attributes
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.app.encode_to(buf);
+        self.kind.encode_to(buf);
+        encode_len(self.attributes.len(), buf);
+        for (key, value) in &self.attributes {
+            key.encode_to(buf);
+            value.encode_to(buf);
+        }
+    }
+}
+
+impl Encode for RevertReason {
+    /// code, message
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.code.encode_to(buf);
+        self.message.encode_to(buf);
+    }
+}
+
+impl Encode for Receipt {
+    /// tx_hash, block_height, index, state, gas_used, fee, error, `u32` count followed by the events
+    ///
+    /// `block_hash` commits to the receipts root, so it is not part of it
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.tx_hash.encode_to(buf);
+        self.block_height.encode_to(buf);
+        self.index.encode_to(buf);
+        self.state.encode_to(buf);
+        self.gas_used.encode_to(buf);
+        self.fee.encode_to(buf);
+        self.error.encode_to(buf);
+        encode_len(self.events.len(), buf);
+        for event in &self.events {
+            event.encode_to(buf);
+        }
+    }
+}
+
+impl Encode for Balances {
+    /// `u32` count followed by the `Token`s sorted by denom
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        encode_len(self.len(), buf);
+        for token in self.iter() {
+            token.encode_to(buf);
+        }
+    }
+}
+
+impl Encode for Account {
+    /// address, nonce, balances
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.address.encode_to(buf);
+        self.nonce.encode_to(buf);
+        self.balances.encode_to(buf);
+    }
+}
+
+impl Encode for DenomMetadata {
+    /// base, display, decimals, symbol
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.base.encode_to(buf);
+        self.display.encode_to(buf);
+        self.decimals.encode_to(buf);
+        self.symbol.encode_to(buf);
+    }
+}
+
+impl Encode for BlockHeader {
+    /// height, timestamp, pre_hash, merkle_root, receipts_root, state_root, validator, reward
+    ///
+    /// `hash` and `signature` are computed from this encoding, so they are not part of it
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.height.encode_to(buf);
+        self.timestamp.encode_to(buf);
+        self.pre_hash.encode_to(buf);
+        self.merkle_root.encode_to(buf);
+        self.receipts_root.encode_to(buf);
+        self.state_root.encode_to(buf);
+        self.validator.encode_to(buf);
+        self.reward.encode_to(buf);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::signature::VerifyKey;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Vectors {
+        tx_body: Vec<Vector<TxBody>>,
+        transaction: Vec<Vector<Transaction>>,
+        block_header: Vec<Vector<BlockHeader>>,
+        receipt: Vec<Vector<Receipt>>
+    }
+
+    #[derive(Deserialize)]
+    struct Vector<T> {
+        value: T,
+        encoded: String
+    }
+
+    fn vectors() -> Vectors {
+        serde_json::from_str(include_str!("encoding_vectors.json")).unwrap()
+    }
+
+    fn make_body(payload: Value) -> TxBody {
+        TxBody {
+            chain_id: "lokichain-1".to_string(),
+            sender: Address {
+                network: "lokichain".to_string(),
+                vk: VerifyKey::Ed25519([1; 32])
+            },
+            data: AppData {
+                app: "bank".to_string(),
+                operation: "transfer".to_string(),
+                payload
+            },
+            amount: Token { value: 10, denom: "LOKI".to_string() },
+            fee: Fee::new(10, Token::new(1, "LOKI")),
+            nonce: 0,
+            valid_after: None,
+            valid_until: None
+        }
+    }
+
+    #[test]
+    fn test_golden_vectors() {
+        let vectors = vectors();
+        for vector in vectors.tx_body {
+            assert_eq!(hex::encode(encode(&vector.value)), vector.encoded);
+        }
+        for vector in vectors.transaction {
+            assert_eq!(hex::encode(encode(&vector.value)), vector.encoded);
+        }
+        for vector in vectors.block_header {
+            assert_eq!(hex::encode(encode(&vector.value)), vector.encoded);
+        }
+        for vector in vectors.receipt {
+            assert_eq!(hex::encode(encode(&vector.value)), vector.encoded);
+        }
+    }
+
+    #[test]
+    fn test_payload_key_order() {
+        let first: Value = serde_json::from_str(r#"{"receiver":"addr","memo":{"b":1,"a":[true,null]}}"#).unwrap();
+        let second: Value = serde_json::from_str(r#"{ "memo": { "a": [true, null], "b": 1 }, "receiver": "addr" }"#).unwrap();
+
+        assert_eq!(encode(&make_body(first)), encode(&make_body(second)));
+    }
+
+    #[test]
+    fn test_payload_types() {
+        let payloads = [
+            serde_json::json!(null),
+            serde_json::json!(false),
+            serde_json::json!(true),
+            serde_json::json!(1),
+            serde_json::json!(-1),
+            serde_json::json!(1.5),
+            serde_json::json!("1"),
+            serde_json::json!([1]),
+            serde_json::json!({"1": 1}),
+        ];
+
+        for (i, first) in payloads.iter().enumerate() {
+            for second in payloads.iter().skip(i + 1) {
+                assert_ne!(encode(first), encode(second));
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_field_ambiguity() {
+        let mut first = make_body(Value::Null);
+        first.data.app = "ba".to_string();
+        first.data.operation = "nktransfer".to_string();
+
+        let second = make_body(Value::Null);
+        assert_ne!(encode(&first), encode(&second));
+    }
+}
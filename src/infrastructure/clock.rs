@@ -0,0 +1,11 @@
+use crate::application::common::clock::Clock;
+use chrono::Utc;
+
+/// Wall clock of the node
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        Utc::now().timestamp() as u64
+    }
+}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InfrastructureError {
+    #[error("Invalid password")]
+    InvalidPassword,
+    #[error("Unsupported keystore: {0}")]
+    UnsupportedKeystore(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,59 @@
+use crate::application::common::hasher::{HashDomain, Hasher};
+use crate::domain::models::hash::Hash;
+use async_trait::async_trait;
+
+/// BLAKE3 hasher in key derivation mode
+///
+/// The domain tag is used as the BLAKE3 context string
+pub struct Blake3Hasher {
+    prefix: blake3::Hasher
+}
+
+impl Blake3Hasher {
+    pub fn new(domain: HashDomain) -> Self {
+        Blake3Hasher { prefix: blake3::Hasher::new_derive_key(domain.tag()) }
+    }
+}
+
+#[async_trait]
+impl Hasher for Blake3Hasher {
+    async fn hash(&self, value: &[u8]) -> Hash {
+        let mut hasher = self.prefix.clone();
+        hasher.update(value);
+        Hash(hasher.finalize().into())
+    }
+
+    async fn verify(&self, value: &[u8], hash: &Hash) -> bool {
+        self.hash(value).await == *hash
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_derive_key_hash() {
+        let hasher = Blake3Hasher::new(HashDomain::Transaction);
+        let value = b"hello world";
+
+        let hash = hasher.hash(value).await;
+        assert_eq!(hash.0, blake3::derive_key("lokichain/transaction/v1", value));
+        assert_ne!(hash.0, *blake3::hash(value).as_bytes());
+        assert!(hasher.verify(value, &hash).await);
+        assert!(!hasher.verify(b"hello world!", &hash).await);
+    }
+
+    #[tokio::test]
+    async fn test_domain_separation() {
+        let value = b"hello world";
+        let tx = Blake3Hasher::new(HashDomain::Transaction).hash(value).await;
+        let header = Blake3Hasher::new(HashDomain::BlockHeader).hash(value).await;
+        let node = Blake3Hasher::new(HashDomain::MerkleNode).hash(value).await;
+
+        assert_ne!(tx, header);
+        assert_ne!(tx, node);
+        assert_ne!(header, node);
+    }
+}
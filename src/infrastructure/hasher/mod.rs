@@ -0,0 +1,54 @@
+pub mod sha256;
+pub mod blake3;
+
+use crate::application::common::config::NodeConfig;
+use crate::application::common::hasher::{HashAlgorithm, HashDomain, Hasher};
+
+/// Creates a `[Hasher]` of `algorithm` bound to the given domain
+pub fn new_hasher(algorithm: HashAlgorithm, domain: HashDomain) -> Box<dyn Hasher> {
+    match algorithm {
+        HashAlgorithm::Sha256 => Box::new(sha256::Sha256Hasher::new(domain)),
+        HashAlgorithm::Blake3 => Box::new(blake3::Blake3Hasher::new(domain)),
+    }
+}
+
+/// Hashers of every domain, all of the algorithm of the node configuration
+pub struct Hashers {
+    pub transaction: Box<dyn Hasher>,
+    pub block_header: Box<dyn Hasher>,
+    pub merkle_node: Box<dyn Hasher>,
+    pub state_node: Box<dyn Hasher>
+}
+
+impl Hashers {
+    pub fn new(config: &NodeConfig) -> Self {
+        Hashers {
+            transaction: new_hasher(config.hash_algorithm, HashDomain::Transaction),
+            block_header: new_hasher(config.hash_algorithm, HashDomain::BlockHeader),
+            merkle_node: new_hasher(config.hash_algorithm, HashDomain::MerkleNode),
+            state_node: new_hasher(config.hash_algorithm, HashDomain::StateNode)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hasher_from_config() {
+        let config: NodeConfig = serde_json::from_str(r#"{ "hash_algorithm": "blake3" }"#).unwrap();
+        assert_eq!(config.hash_algorithm, HashAlgorithm::Blake3);
+        assert_eq!(NodeConfig::default().hash_algorithm, HashAlgorithm::Sha256);
+
+        let value = b"hello world";
+        let sha256 = Hashers::new(&NodeConfig::default());
+        let blake3 = Hashers::new(&config);
+        assert_ne!(sha256.transaction.hash(value).await, blake3.transaction.hash(value).await);
+        assert_eq!(blake3.transaction.hash(value).await, blake3::Blake3Hasher::new(HashDomain::Transaction).hash(value).await);
+        // the domains of the same algorithm never collide
+        assert_ne!(blake3.transaction.hash(value).await, blake3.block_header.hash(value).await);
+        assert_ne!(blake3.merkle_node.hash(value).await, blake3.state_node.hash(value).await);
+    }
+}
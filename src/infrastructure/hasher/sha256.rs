@@ -0,0 +1,81 @@
+use crate::application::common::hasher::{HashDomain, Hasher};
+use crate::domain::models::hash::Hash;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+/// SHA-256 tagged hasher
+///
+/// `SHA256(SHA256(tag) || SHA256(tag) || value)`, the same construction as BIP-340 tagged hashes
+pub struct Sha256Hasher {
+    prefix: Sha256
+}
+
+impl Sha256Hasher {
+    pub fn new(domain: HashDomain) -> Self {
+        let tag = Sha256::digest(domain.tag().as_bytes());
+        let mut prefix = Sha256::new();
+        prefix.update(tag);
+        prefix.update(tag);
+        Sha256Hasher { prefix }
+    }
+}
+
+#[async_trait]
+impl Hasher for Sha256Hasher {
+    async fn hash(&self, value: &[u8]) -> Hash {
+        let mut hasher = self.prefix.clone();
+        hasher.update(value);
+        Hash(hasher.finalize().into())
+    }
+
+    async fn verify(&self, value: &[u8], hash: &Hash) -> bool {
+        self.hash(value).await == *hash
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tagged_hash() {
+        let hasher = Sha256Hasher::new(HashDomain::Transaction);
+        let value = b"hello world";
+
+        let tag = Sha256::digest(b"lokichain/transaction/v1");
+        let expected = Sha256::new()
+            .chain_update(tag)
+            .chain_update(tag)
+            .chain_update(value)
+            .finalize();
+
+        let hash = hasher.hash(value).await;
+        assert_eq!(hash.0, <[u8; 32]>::from(expected));
+        assert!(hasher.verify(value, &hash).await);
+        assert!(!hasher.verify(b"hello world!", &hash).await);
+    }
+
+    #[tokio::test]
+    async fn test_domain_separation() {
+        let value = b"hello world";
+        let tx = Sha256Hasher::new(HashDomain::Transaction).hash(value).await;
+        let header = Sha256Hasher::new(HashDomain::BlockHeader).hash(value).await;
+        let node = Sha256Hasher::new(HashDomain::MerkleNode).hash(value).await;
+
+        assert_ne!(tx, header);
+        assert_ne!(tx, node);
+        assert_ne!(header, node);
+    }
+
+    #[tokio::test]
+    async fn test_no_truncation() {
+        let hasher = Sha256Hasher::new(HashDomain::Transaction);
+        let mut first = [0u8; 40];
+        let mut second = [0u8; 40];
+        second[39] = 1;
+        first[0] = 7;
+        second[0] = 7;
+        assert_ne!(hasher.hash(&first).await, hasher.hash(&second).await);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::application::common::signer::Signer;
+use crate::domain::models::signature::{SecretSignKey, SignKey, VerifyKey};
+use crate::infrastructure::exceptions::InfrastructureError;
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, Payload};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+/// Version of the keystore envelope
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF: &str = "argon2id";
+const CIPHER: &str = "xchacha20poly1305";
+
+/// Argon2id cost parameters
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// memory size in KiB
+    pub m_cost: u32,
+    /// number of iterations
+    pub t_cost: u32,
+    /// degree of parallelism
+    pub p_cost: u32
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    /// key derivation function; ex: `argon2id`
+    pub kdf: String,
+    pub kdf_params: KdfParams,
+    /// base64 salt of the kdf
+    pub salt: String,
+    /// authenticated cipher; ex: `xchacha20poly1305`
+    pub cipher: String,
+    /// base64 nonce of the cipher
+    pub nonce: String,
+    /// base64 encrypted scheme tagged `[SignKey]` with the authentication tag
+    pub ciphertext: String
+}
+
+/// Password encrypted `[SignKey]` file
+///
+/// for example:
+/// ```json
+/// {
+///   "version": 1,
+///   "vk": "...",
+///   "crypto": {
+///     "kdf": "argon2id",
+///     "kdf_params": { "m_cost": 65536, "t_cost": 3, "p_cost": 1 },
+///     "salt": "...",
+///     "cipher": "xchacha20poly1305",
+///     "nonce": "...",
+///     "ciphertext": "..."
+///   }
+/// }
+/// ```
+///
+/// The version, verify key and kdf settings are authenticated as associated data
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// public key of the stored `[SignKey]`, readable without the password
+    pub vk: VerifyKey,
+    pub crypto: KeystoreCrypto
+}
+
+impl Keystore {
+    /// Encrypts the key, `signer` must support the scheme of `sign_key`
+    pub async fn encrypt(
+        signer: &dyn Signer,
+        sign_key: &SignKey,
+        password: &str,
+        params: KdfParams
+    ) -> Result<Self, InfrastructureError> {
+        let vk = signer.verify_key(sign_key).await;
+        Keystore::seal(&Zeroizing::new(sign_key.to_bytes()), vk, password, params)
+    }
+
+    /// Encrypts `sign_key`, the tagged bytes of a `[SignKey]`
+    fn seal(sign_key: &[u8], vk: VerifyKey, password: &str, params: KdfParams) -> Result<Self, InfrastructureError> {
+        let salt: [u8; 16] = rand::random();
+        let nonce: [u8; 24] = rand::random();
+
+        let mut keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            vk,
+            crypto: KeystoreCrypto {
+                kdf: KDF.to_string(),
+                kdf_params: params,
+                salt: STANDARD_NO_PAD.encode(salt),
+                cipher: CIPHER.to_string(),
+                nonce: STANDARD_NO_PAD.encode(nonce),
+                ciphertext: String::new()
+            }
+        };
+
+        let key = keystore.derive_key(password, &salt)?;
+        let ciphertext = XChaCha20Poly1305::new(key.as_ref().into())
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: sign_key, aad: &keystore.aad()? })
+            .map_err(|_| InfrastructureError::UnsupportedKeystore("encryption failed".to_string()))?;
+        keystore.crypto.ciphertext = STANDARD_NO_PAD.encode(ciphertext);
+
+        Ok(keystore)
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<SecretSignKey, InfrastructureError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(InfrastructureError::UnsupportedKeystore(format!("version {}", self.version)));
+        }
+        if self.crypto.kdf != KDF || self.crypto.cipher != CIPHER {
+            return Err(InfrastructureError::UnsupportedKeystore(
+                format!("{} with {}", self.crypto.kdf, self.crypto.cipher)
+            ));
+        }
+
+        let salt = decode_field("salt", &self.crypto.salt)?;
+        let nonce = decode_field("nonce", &self.crypto.nonce)?;
+        let ciphertext = decode_field("ciphertext", &self.crypto.ciphertext)?;
+        if nonce.len() != 24 {
+            return Err(InfrastructureError::UnsupportedKeystore("nonce must be 24 bytes".to_string()));
+        }
+
+        let key = self.derive_key(password, &salt)?;
+        let plaintext = Zeroizing::new(
+            XChaCha20Poly1305::new(key.as_ref().into())
+                .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &self.aad()? })
+                .map_err(|_| InfrastructureError::InvalidPassword)?
+        );
+
+        let sign_key = SignKey::from_bytes(&plaintext)
+            .map_err(|err| InfrastructureError::UnsupportedKeystore(err.to_string()))?;
+        Ok(SecretSignKey::new(sign_key))
+    }
+
+    /// Re-encrypts the key with a new password, salt and nonce
+    pub fn change_password(&self, old_password: &str, new_password: &str) -> Result<Self, InfrastructureError> {
+        let sign_key = self.decrypt(old_password)?;
+        Keystore::seal(&Zeroizing::new(sign_key.to_bytes()), self.vk.clone(), new_password, self.crypto.kdf_params.clone())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, InfrastructureError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes the keystore, on unix the file is readable by the owner only
+    pub fn save(&self, path: &Path) -> Result<(), InfrastructureError> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, InfrastructureError> {
+        let params = &self.crypto.kdf_params;
+        let params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(32))
+            .map_err(|err| InfrastructureError::UnsupportedKeystore(err.to_string()))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), salt, key.as_mut())
+            .map_err(|err| InfrastructureError::UnsupportedKeystore(err.to_string()))?;
+        Ok(key)
+    }
+
+    fn aad(&self) -> Result<Vec<u8>, InfrastructureError> {
+        Ok(serde_json::to_vec(&(
+            self.version,
+            &self.vk,
+            &self.crypto.kdf,
+            &self.crypto.kdf_params,
+            &self.crypto.salt,
+            &self.crypto.cipher
+        ))?)
+    }
+}
+
+fn decode_field(name: &str, value: &str) -> Result<Vec<u8>, InfrastructureError> {
+    STANDARD_NO_PAD.decode(value)
+        .map_err(|err| InfrastructureError::UnsupportedKeystore(format!("{}: {}", name, err)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::signer::tests::MockSigner;
+    use crate::domain::models::signature::{Secp256k1Scalar, SignatureScheme};
+    use crate::infrastructure::signer::ed25519::Ed25519Signer;
+
+    /// Cheap parameters to keep tests fast
+    fn test_params() -> KdfParams {
+        KdfParams { m_cost: 64, t_cost: 1, p_cost: 1 }
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_decrypt() {
+        let sign_key = Ed25519Signer::generate_sign_key();
+        let keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "password", test_params()).await.unwrap();
+
+        assert_eq!(keystore.version, KEYSTORE_VERSION);
+        assert_eq!(keystore.vk, Ed25519Signer::keypair(&sign_key).1);
+        assert!(!keystore.crypto.ciphertext.contains(&STANDARD_NO_PAD.encode(sign_key.as_bytes())));
+        assert_eq!(*keystore.decrypt("password").unwrap(), sign_key);
+        assert!(matches!(keystore.decrypt("wrong"), Err(InfrastructureError::InvalidPassword)));
+    }
+
+    #[tokio::test]
+    async fn test_tampered_params() {
+        let sign_key = Ed25519Signer::generate_sign_key();
+        let mut keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "password", test_params()).await.unwrap();
+        keystore.crypto.kdf_params.t_cost = 2;
+        assert!(keystore.decrypt("password").is_err());
+
+        let mut keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "password", test_params()).await.unwrap();
+        keystore.vk = VerifyKey::Secp256k1([2; 33]);
+        assert!(keystore.decrypt("password").is_err());
+
+        let mut keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "password", test_params()).await.unwrap();
+        keystore.version = 2;
+        assert!(matches!(keystore.decrypt("password"), Err(InfrastructureError::UnsupportedKeystore(_))));
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let sign_key = Ed25519Signer::generate_sign_key();
+        let keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "old", test_params()).await.unwrap();
+        let changed = keystore.change_password("old", "new").unwrap();
+
+        assert_ne!(changed.crypto.salt, keystore.crypto.salt);
+        assert_eq!(*changed.decrypt("new").unwrap(), sign_key);
+        assert!(changed.decrypt("old").is_err());
+        assert!(keystore.change_password("wrong", "new").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_other_scheme() {
+        let sign_key = SignKey::Secp256k1(Secp256k1Scalar::new([7; 32]).unwrap());
+        let keystore = Keystore::seal(&sign_key.to_bytes(), VerifyKey::Secp256k1([2; 33]), "password", test_params()).unwrap();
+        assert_eq!(*keystore.decrypt("password").unwrap(), sign_key);
+
+        let sign_key = SignKey::Ed25519([7; 32]);
+        let keystore = Keystore::encrypt(&MockSigner, &sign_key, "password", test_params()).await.unwrap();
+        assert_eq!(keystore.vk, VerifyKey::Ed25519([7; 32]));
+        assert_eq!(*keystore.decrypt("password").unwrap(), sign_key);
+
+        // a scalar out of the secp256k1 range is rejected instead of reaching the signer
+        let out_of_range = [&[SignatureScheme::Secp256k1.tag()], [0xff; 32].as_slice()].concat();
+        let keystore = Keystore::seal(&out_of_range, VerifyKey::Secp256k1([2; 33]), "password", test_params()).unwrap();
+        assert!(matches!(keystore.decrypt("password"), Err(InfrastructureError::UnsupportedKeystore(_))));
+    }
+
+    #[tokio::test]
+    async fn test_save_load() {
+        let sign_key = Ed25519Signer::generate_sign_key();
+        let keystore = Keystore::encrypt(&Ed25519Signer, &sign_key, "password", test_params()).await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("lokichain-keystore-{}.json", hex::encode(rand::random::<[u8; 8]>())));
+        keystore.save(&path).unwrap();
+        let loaded = Keystore::load(&path);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded, keystore);
+        assert_eq!(*loaded.decrypt("password").unwrap(), sign_key);
+    }
+}
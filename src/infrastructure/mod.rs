@@ -0,0 +1,6 @@
+pub mod clock;
+pub mod exceptions;
+pub mod hasher;
+pub mod keystore;
+pub mod signer;
+pub mod wallet;
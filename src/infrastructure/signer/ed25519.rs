@@ -0,0 +1,154 @@
+use crate::application::common::signer::Signer;
+use crate::domain::models::signature::{SignKey, Signature, VerifyKey};
+use async_trait::async_trait;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+
+/// Ed25519 signer (RFC 8032)
+///
+/// `[SignKey]` is the 32 byte secret seed, `[VerifyKey]` is the compressed public point.
+/// Keys and signatures of other schemes never verify
+pub struct Ed25519Signer;
+
+impl Ed25519Signer {
+    pub fn new() -> Self {
+        Ed25519Signer
+    }
+
+    /// Generates a new random `[SignKey]` using the OS entropy source
+    pub fn generate_sign_key() -> SignKey {
+        SignKey::Ed25519(SigningKey::generate(&mut OsRng).to_bytes())
+    }
+
+    /// Returns the keypair (`[SignKey]`, `[VerifyKey]`) for the given secret seed
+    ///
+    /// # Panics
+    /// If `sign_key` is not an Ed25519 key
+    pub fn keypair(sign_key: &SignKey) -> (SignKey, VerifyKey) {
+        let signing_key = signing_key(sign_key);
+        (sign_key.clone(), VerifyKey::Ed25519(signing_key.verifying_key().to_bytes()))
+    }
+}
+
+fn signing_key(sign_key: &SignKey) -> SigningKey {
+    match sign_key {
+        SignKey::Ed25519(seed) => SigningKey::from_bytes(seed),
+        _ => panic!("Ed25519Signer can not use {:?} keys", sign_key.scheme()),
+    }
+}
+
+impl Default for Ed25519Signer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Signer for Ed25519Signer {
+    async fn sign(&self, data: &[u8], sign_key: &SignKey) -> Signature {
+        let signing_key = signing_key(sign_key);
+        Signature::Ed25519(ed25519_dalek::Signer::sign(&signing_key, data).to_bytes())
+    }
+
+    async fn verify_key(&self, sign_key: &SignKey) -> VerifyKey {
+        Self::keypair(sign_key).1
+    }
+
+    /// Strict verification: rejects non-canonical signatures and small order keys
+    async fn verify(&self, data: &[u8], signature: &Signature, verify_key: &VerifyKey) -> bool {
+        let (VerifyKey::Ed25519(verify_key), Signature::Ed25519(signature)) = (verify_key, signature) else {
+            return false;
+        };
+        let Ok(verifying_key) = VerifyingKey::from_bytes(verify_key) else {
+            return false;
+        };
+        let signature = ed25519_dalek::Signature::from_bytes(signature);
+        verifying_key.verify_strict(data, &signature).is_ok()
+    }
+
+    async fn recover(&self, _data: &[u8], _signature: &Signature) -> Option<VerifyKey> {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors from RFC 8032, section 7.1: (secret key, public key, message, signature)
+    const RFC8032_VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    fn decode<const N: usize>(value: &str) -> [u8; N] {
+        <[u8; N]>::try_from(hex::decode(value).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rfc8032_vectors() {
+        let signer = Ed25519Signer::new();
+
+        for (sk, vk, message, signature) in RFC8032_VECTORS {
+            let sign_key = SignKey::Ed25519(decode(sk));
+            let verify_key = VerifyKey::Ed25519(decode(vk));
+            let message = hex::decode(message).unwrap();
+            let expected = Signature::Ed25519(decode(signature));
+
+            assert_eq!(signer.verify_key(&sign_key).await, verify_key);
+            assert_eq!(signer.sign(&message, &sign_key).await, expected);
+            assert!(signer.verify(&message, &expected, &verify_key).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_verify() {
+        let signer = Ed25519Signer::new();
+        let (sign_key, verify_key) = Ed25519Signer::keypair(&Ed25519Signer::generate_sign_key());
+        let data = b"hello world";
+
+        let signature = signer.sign(data, &sign_key).await;
+        assert!(signer.verify(data, &signature, &verify_key).await);
+        assert!(!signer.verify(b"hello world!", &signature, &verify_key).await);
+    }
+
+    #[tokio::test]
+    async fn test_reject_forged() {
+        let signer = Ed25519Signer::new();
+        let (sign_key, verify_key) = Ed25519Signer::keypair(&Ed25519Signer::generate_sign_key());
+        let other_key = signer.verify_key(&Ed25519Signer::generate_sign_key()).await;
+        let data = b"hello world";
+
+        let signature = signer.sign(data, &sign_key).await;
+        assert!(!signer.verify(data, &signature, &other_key).await);
+
+        let mut forged = signature.to_bytes();
+        forged[0] ^= 1;
+        let forged = Signature::from_bytes(&forged).unwrap();
+        assert!(!signer.verify(data, &forged, &verify_key).await);
+
+        // keys and signatures of other schemes
+        assert!(!signer.verify(data, &signature, &VerifyKey::Secp256k1([2; 33])).await);
+        assert!(!signer.verify(data, &Signature::Secp256k1([0; 65]), &verify_key).await);
+
+        // small order point is rejected by strict verification
+        assert!(!signer.verify(data, &Signature::Ed25519([0; 64]), &VerifyKey::Ed25519([0; 32])).await);
+    }
+}
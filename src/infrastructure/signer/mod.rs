@@ -0,0 +1,216 @@
+pub mod ed25519;
+pub mod secp256k1;
+
+use crate::application::common::signer::Signer;
+use crate::domain::models::signature::{MultisigKey, SignKey, Signature, SignatureScheme, VerifyKey};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// `[Signer]` that dispatches every call to the signer registered for the scheme of the key
+///
+/// Verification fails if the signature and the key have different schemes
+/// or no signer is registered for the scheme.
+/// Multisig signatures are verified member by member with the signers of the member schemes
+pub struct SchemeSigner {
+    signers: HashMap<SignatureScheme, Box<dyn Signer>>
+}
+
+impl SchemeSigner {
+    pub fn new() -> Self {
+        SchemeSigner { signers: HashMap::new() }
+    }
+
+    pub fn register(mut self, scheme: SignatureScheme, signer: Box<dyn Signer>) -> Self {
+        self.signers.insert(scheme, signer);
+        self
+    }
+
+    /// # Panics
+    /// If no signer is registered for the scheme, it is a node configuration error
+    fn signer(&self, scheme: SignatureScheme) -> &dyn Signer {
+        self.signers
+            .get(&scheme)
+            .unwrap_or_else(|| panic!("no signer registered for {:?}", scheme))
+            .as_ref()
+    }
+
+    /// Every signature must be valid for a distinct member key, indexes strictly ascending,
+    /// and there must be at least `threshold` of them
+    async fn verify_multisig(&self, data: &[u8], signatures: &[(u8, Signature)], key: &MultisigKey) -> bool {
+        if signatures.len() < key.threshold() as usize {
+            return false;
+        }
+        if signatures.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return false;
+        }
+        for (index, signature) in signatures {
+            let Some(verify_key) = key.keys().get(*index as usize) else {
+                return false;
+            };
+            if !self.verify(data, signature, verify_key).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Default for SchemeSigner {
+    /// Signer with every built-in scheme
+    fn default() -> Self {
+        SchemeSigner::new()
+            .register(SignatureScheme::Ed25519, Box::new(ed25519::Ed25519Signer::new()))
+            .register(SignatureScheme::Secp256k1, Box::new(secp256k1::Secp256k1Signer::new()))
+    }
+}
+
+#[async_trait]
+impl Signer for SchemeSigner {
+    async fn sign(&self, data: &[u8], sign_key: &SignKey) -> Signature {
+        self.signer(sign_key.scheme()).sign(data, sign_key).await
+    }
+
+    async fn verify_key(&self, sign_key: &SignKey) -> VerifyKey {
+        self.signer(sign_key.scheme()).verify_key(sign_key).await
+    }
+
+    async fn verify(&self, data: &[u8], signature: &Signature, verify_key: &VerifyKey) -> bool {
+        if let (Signature::Multisig(signatures), VerifyKey::Multisig(key)) = (signature, verify_key) {
+            return self.verify_multisig(data, signatures, key).await;
+        }
+        if signature.scheme() != verify_key.scheme() {
+            return false;
+        }
+        match self.signers.get(&verify_key.scheme()) {
+            Some(signer) => signer.verify(data, signature, verify_key).await,
+            None => false,
+        }
+    }
+
+    async fn recover(&self, data: &[u8], signature: &Signature) -> Option<VerifyKey> {
+        self.signers.get(&signature.scheme())?.recover(data, signature).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::signer::ed25519::Ed25519Signer;
+    use crate::infrastructure::signer::secp256k1::Secp256k1Signer;
+    use crate::domain::models::signature::Secp256k1Scalar;
+
+    /// Accepts every signature of any scheme
+    struct AcceptSigner;
+
+    #[async_trait]
+    impl Signer for AcceptSigner {
+        async fn sign(&self, _data: &[u8], _sign_key: &SignKey) -> Signature {
+            Signature::Secp256k1([0; 65])
+        }
+
+        async fn verify_key(&self, _sign_key: &SignKey) -> VerifyKey {
+            VerifyKey::Secp256k1([2; 33])
+        }
+
+        async fn verify(&self, _data: &[u8], _signature: &Signature, _verify_key: &VerifyKey) -> bool {
+            true
+        }
+
+        async fn recover(&self, _data: &[u8], _signature: &Signature) -> Option<VerifyKey> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch() {
+        let signer = SchemeSigner::new()
+            .register(SignatureScheme::Ed25519, Box::new(Ed25519Signer::new()));
+        let (sign_key, verify_key) = Ed25519Signer::keypair(&Ed25519Signer::generate_sign_key());
+        let data = b"hello world";
+
+        let signature = signer.sign(data, &sign_key).await;
+        assert_eq!(signer.verify_key(&sign_key).await, verify_key);
+        assert!(signer.verify(data, &signature, &verify_key).await);
+        assert!(!signer.verify(data, &Signature::Secp256k1([0; 65]), &VerifyKey::Secp256k1([2; 33])).await);
+
+        let signer = signer.register(SignatureScheme::Secp256k1, Box::new(AcceptSigner));
+        assert!(signer.verify(data, &Signature::Secp256k1([0; 65]), &VerifyKey::Secp256k1([2; 33])).await);
+        assert!(!signer.verify(data, &Signature::Secp256k1([0; 65]), &verify_key).await);
+        assert!(!signer.verify(data, &signature, &VerifyKey::Secp256k1([2; 33])).await);
+    }
+
+    #[tokio::test]
+    async fn test_default_schemes() {
+        let signer = SchemeSigner::default();
+        let data = b"hello world";
+
+        for sign_key in [Ed25519Signer::generate_sign_key(), Secp256k1Signer::generate_sign_key()] {
+            let verify_key = signer.verify_key(&sign_key).await;
+            let signature = signer.sign(data, &sign_key).await;
+            assert_eq!(signature.scheme(), sign_key.scheme());
+            assert!(signer.verify(data, &signature, &verify_key).await);
+        }
+
+        let sign_key = Secp256k1Signer::generate_sign_key();
+        let signature = signer.sign(data, &sign_key).await;
+        assert_eq!(signer.recover(data, &signature).await, Some(signer.verify_key(&sign_key).await));
+    }
+
+    #[tokio::test]
+    async fn test_multisig() {
+        let signer = SchemeSigner::default();
+        let data = b"hello world";
+
+        // three of five officers, with both schemes
+        let sign_keys = vec![
+            Ed25519Signer::generate_sign_key(),
+            Ed25519Signer::generate_sign_key(),
+            Ed25519Signer::generate_sign_key(),
+            Secp256k1Signer::generate_sign_key(),
+            Secp256k1Signer::generate_sign_key(),
+        ];
+        let mut verify_keys = vec![];
+        for sign_key in &sign_keys {
+            verify_keys.push(signer.verify_key(sign_key).await);
+        }
+        let key = MultisigKey::new(3, verify_keys.clone()).unwrap();
+        let verify_key = key.to_verify_key();
+
+        let mut signatures = vec![];
+        for (sign_key, member) in sign_keys.iter().zip(&verify_keys) {
+            signatures.push((key.index_of(member).unwrap(), signer.sign(data, sign_key).await));
+        }
+        signatures.sort_by_key(|(index, _)| *index);
+
+        let multisig = |signatures: &[(u8, Signature)]| Signature::Multisig(signatures.to_vec());
+        assert!(signer.verify(data, &multisig(&signatures[..3]), &verify_key).await);
+        assert!(signer.verify(data, &multisig(&signatures[1..]), &verify_key).await);
+        assert!(signer.verify(data, &multisig(&signatures), &verify_key).await);
+        assert!(!signer.verify(data, &multisig(&signatures[..2]), &verify_key).await);
+        assert!(!signer.verify(b"hello world!", &multisig(&signatures[..3]), &verify_key).await);
+
+        // the same member can not sign twice
+        let duplicated = [signatures[0].clone(), signatures[0].clone(), signatures[1].clone()];
+        assert!(!signer.verify(data, &multisig(&duplicated), &verify_key).await);
+
+        // a valid signature under the wrong index, or an index out of range
+        let mut swapped = signatures[..3].to_vec();
+        swapped[0].1 = signatures[3].1.clone();
+        assert!(!signer.verify(data, &multisig(&swapped), &verify_key).await);
+        let mut out_of_range = signatures[..3].to_vec();
+        out_of_range[2].0 = 5;
+        assert!(!signer.verify(data, &multisig(&out_of_range), &verify_key).await);
+
+        // unordered indexes, and a single signature for the multisig key
+        let unordered = [signatures[2].clone(), signatures[0].clone(), signatures[1].clone()];
+        assert!(!signer.verify(data, &multisig(&unordered), &verify_key).await);
+        assert!(!signer.verify(data, &signatures[0].1, &verify_key).await);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no signer registered for Secp256k1")]
+    async fn test_sign_unregistered() {
+        SchemeSigner::new().sign(b"hello world", &SignKey::Secp256k1(Secp256k1Scalar::new([1; 32]).unwrap())).await;
+    }
+}
@@ -0,0 +1,176 @@
+use crate::application::common::signer::Signer;
+use crate::domain::models::signature::{Secp256k1Scalar, SignKey, Signature, VerifyKey};
+use async_trait::async_trait;
+use k256::ecdsa::{RecoveryId, SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+
+/// secp256k1 ECDSA signer with public key recovery
+///
+/// Signs `SHA256(data)` with RFC 6979 nonces. The `[Signature]` is `r || s || recovery_id`,
+/// `s` is always normalized to the lower half of the curve order, high `s` never verifies.
+/// Keys and signatures of other schemes never verify
+pub struct Secp256k1Signer;
+
+impl Secp256k1Signer {
+    pub fn new() -> Self {
+        Secp256k1Signer
+    }
+
+    /// Generates a new random `[SignKey]` using the OS entropy source
+    pub fn generate_sign_key() -> SignKey {
+        let scalar = Secp256k1Scalar::new(SigningKey::random(&mut OsRng).to_bytes().into())
+            .expect("a random SigningKey is a valid scalar");
+        SignKey::Secp256k1(scalar)
+    }
+
+    /// Returns the keypair (`[SignKey]`, `[VerifyKey]`) for the given secret scalar
+    ///
+    /// # Panics
+    /// If `sign_key` is not a secp256k1 key
+    pub fn keypair(sign_key: &SignKey) -> (SignKey, VerifyKey) {
+        (sign_key.clone(), to_verify_key(signing_key(sign_key).verifying_key()))
+    }
+
+    /// Recovers the key that produced `signature` over `data`
+    pub fn recover_key(data: &[u8], signature: &Signature) -> Option<VerifyKey> {
+        let Signature::Secp256k1(bytes) = signature else {
+            return None;
+        };
+        let ecdsa = k256::ecdsa::Signature::from_slice(&bytes[..64]).ok()?;
+        if ecdsa.normalize_s().is_some() {
+            return None;
+        }
+        let recovery_id = RecoveryId::from_byte(bytes[64])?;
+        VerifyingKey::recover_from_msg(data, &ecdsa, recovery_id)
+            .ok()
+            .map(|key| to_verify_key(&key))
+    }
+}
+
+impl Default for Secp256k1Signer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn signing_key(sign_key: &SignKey) -> SigningKey {
+    match sign_key {
+        // `[Secp256k1Scalar]` can only hold a valid scalar
+        SignKey::Secp256k1(scalar) => SigningKey::from_bytes(scalar.as_bytes().into())
+            .expect("Secp256k1Scalar is a non-zero scalar below the curve order"),
+        _ => panic!("Secp256k1Signer can not use {:?} keys", sign_key.scheme()),
+    }
+}
+
+fn to_verify_key(key: &VerifyingKey) -> VerifyKey {
+    let point = key.to_encoded_point(true);
+    VerifyKey::Secp256k1(point.as_bytes().try_into().expect("compressed point is 33 bytes"))
+}
+
+#[async_trait]
+impl Signer for Secp256k1Signer {
+    async fn sign(&self, data: &[u8], sign_key: &SignKey) -> Signature {
+        let (signature, recovery_id) = signing_key(sign_key)
+            .sign_recoverable(data)
+            .expect("secp256k1 signing with a valid key can not fail");
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(&signature.to_bytes());
+        bytes[64] = recovery_id.to_byte();
+        Signature::Secp256k1(bytes)
+    }
+
+    async fn verify_key(&self, sign_key: &SignKey) -> VerifyKey {
+        Self::keypair(sign_key).1
+    }
+
+    async fn verify(&self, data: &[u8], signature: &Signature, verify_key: &VerifyKey) -> bool {
+        match verify_key {
+            VerifyKey::Secp256k1(_) => Self::recover_key(data, signature).as_ref() == Some(verify_key),
+            _ => false,
+        }
+    }
+
+    async fn recover(&self, data: &[u8], signature: &Signature) -> Option<VerifyKey> {
+        Self::recover_key(data, signature)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::elliptic_curve::scalar::IsHigh;
+
+    #[tokio::test]
+    async fn test_generator_key() {
+        let mut scalar = [0u8; 32];
+        scalar[31] = 1;
+        let verify_key = Secp256k1Signer.verify_key(&SignKey::Secp256k1(Secp256k1Scalar::new(scalar).unwrap())).await;
+        let expected = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(verify_key, VerifyKey::Secp256k1(expected.try_into().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_sign_verify_recover() {
+        let signer = Secp256k1Signer::new();
+        let (sign_key, verify_key) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+        let data = b"hello world";
+
+        let signature = signer.sign(data, &sign_key).await;
+        assert_eq!(signer.sign(data, &sign_key).await, signature);
+        assert!(signer.verify(data, &signature, &verify_key).await);
+        assert!(!signer.verify(b"hello world!", &signature, &verify_key).await);
+        assert_eq!(signer.recover(data, &signature).await, Some(verify_key.clone()));
+
+        let other_key = signer.verify_key(&Secp256k1Signer::generate_sign_key()).await;
+        assert!(!signer.verify(data, &signature, &other_key).await);
+    }
+
+    #[tokio::test]
+    async fn test_low_s() {
+        let signer = Secp256k1Signer::new();
+        let (sign_key, verify_key) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+        let data = b"hello world";
+
+        for _ in 0..8 {
+            let data = rand::random::<[u8; 32]>();
+            let Signature::Secp256k1(bytes) = signer.sign(&data, &sign_key).await else {
+                panic!("unexpected scheme");
+            };
+            let ecdsa = k256::ecdsa::Signature::from_slice(&bytes[..64]).unwrap();
+            assert!(!bool::from(ecdsa.s().is_high()));
+        }
+
+        // the same signature with `s` negated and the recovery parity flipped is valid ECDSA, but not canonical
+        let Signature::Secp256k1(bytes) = signer.sign(data, &sign_key).await else {
+            panic!("unexpected scheme");
+        };
+        let ecdsa = k256::ecdsa::Signature::from_slice(&bytes[..64]).unwrap();
+        let high = k256::ecdsa::Signature::from_scalars(ecdsa.r(), -*ecdsa.s()).unwrap();
+        let mut malleated = [0u8; 65];
+        malleated[..64].copy_from_slice(&high.to_bytes());
+        malleated[64] = bytes[64] ^ 1;
+
+        let malleated = Signature::Secp256k1(malleated);
+        assert!(!signer.verify(data, &malleated, &verify_key).await);
+        assert_eq!(signer.recover(data, &malleated).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_other_scheme() {
+        let signer = Secp256k1Signer::new();
+        let (sign_key, verify_key) = Secp256k1Signer::keypair(&Secp256k1Signer::generate_sign_key());
+        let data = b"hello world";
+        let signature = signer.sign(data, &sign_key).await;
+
+        assert!(!signer.verify(data, &signature, &VerifyKey::Ed25519([1; 32])).await);
+        assert!(!signer.verify(data, &Signature::Ed25519([1; 64]), &verify_key).await);
+        assert_eq!(signer.recover(data, &Signature::Ed25519([1; 64])).await, None);
+
+        // recovery id out of range
+        let mut invalid = signature.to_bytes();
+        invalid[65] = 4;
+        assert!(!signer.verify(data, &Signature::from_bytes(&invalid).unwrap(), &verify_key).await);
+    }
+}
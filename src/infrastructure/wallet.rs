@@ -0,0 +1,148 @@
+use crate::domain::exceptions::DomainError;
+use crate::domain::models::address::{Address, RawAddress};
+use crate::domain::models::signature::{SignKey, VerifyKey};
+use crate::infrastructure::signer::ed25519::Ed25519Signer;
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+/// HMAC key of the SLIP-10 Ed25519 master node
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// Hierarchical deterministic wallet (BIP-39 seed, SLIP-10 Ed25519 derivation)
+///
+/// One mnemonic restores every account derived from it by `[RawAddress]`
+pub struct HdWallet {
+    seed: Vec<u8>
+}
+
+impl HdWallet {
+    /// Generates a new random english mnemonic of 12, 15, 18, 21 or 24 words
+    pub fn generate_mnemonic(word_count: usize) -> Result<String, DomainError> {
+        if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+            return Err(DomainError::InvalidMnemonic(format!("unsupported word count {}", word_count)));
+        }
+        let entropy: [u8; 32] = rand::random();
+        let mnemonic = Mnemonic::from_entropy(&entropy[..word_count / 3 * 4])
+            .map_err(|err| DomainError::InvalidMnemonic(err.to_string()))?;
+        Ok(mnemonic.to_string())
+    }
+
+    /// Checks words and checksum of the mnemonic
+    pub fn validate_mnemonic(phrase: &str) -> Result<(), DomainError> {
+        Mnemonic::parse(phrase)
+            .map(|_| ())
+            .map_err(|err| DomainError::InvalidMnemonic(err.to_string()))
+    }
+
+    /// Restores the wallet from a mnemonic and an optional passphrase (empty string if none)
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self, DomainError> {
+        let mnemonic = Mnemonic::parse(phrase)
+            .map_err(|err| DomainError::InvalidMnemonic(err.to_string()))?;
+        Ok(HdWallet::from_seed(&mnemonic.to_seed(passphrase)))
+    }
+
+    pub fn from_seed(seed: &[u8]) -> Self {
+        HdWallet { seed: seed.to_vec() }
+    }
+
+    /// Derives the keypair and the address for the path
+    pub fn derive(&self, raw: &RawAddress) -> (SignKey, VerifyKey, Address) {
+        let sign_key = self.derive_sign_key(&raw.indexes());
+        let (sign_key, vk) = Ed25519Signer::keypair(&sign_key);
+        let address = Address {
+            network: raw.network.clone(),
+            vk: vk.clone()
+        };
+        (sign_key, vk, address)
+    }
+
+    /// SLIP-10 Ed25519 derivation, every index must be hardened
+    fn derive_sign_key(&self, indexes: &[u32]) -> SignKey {
+        let (mut key, mut chain_code) = hmac_sha512(ED25519_SEED_KEY, &[&self.seed]);
+        for index in indexes {
+            (key, chain_code) = hmac_sha512(&chain_code, &[&[0], &key, &index.to_be_bytes()]);
+        }
+        SignKey::Ed25519(key)
+    }
+}
+
+/// Returns the left and right halves of `HMAC-SHA512(key, data)`
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    let result = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+    (left, right)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::address::HARDENED;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_bip39_seed() {
+        let wallet = HdWallet::from_mnemonic(MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(wallet.seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    /// SLIP-10 test vector 1 for Ed25519
+    #[test]
+    fn test_slip10_vector() {
+        let wallet = HdWallet::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap());
+        let derive = |indexes: &[u32]| hex::encode(wallet.derive_sign_key(indexes).as_bytes());
+
+        assert_eq!(derive(&[]), "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+        assert_eq!(derive(&[HARDENED]), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_eq!(
+            derive(&[HARDENED, 1 | HARDENED, 2 | HARDENED]),
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"
+        );
+
+        let raw = RawAddress::from_path("lokichain", "m/0'/1'/2'/2'/1000000000'").unwrap();
+        let (sign_key, vk, address) = wallet.derive(&raw);
+        assert_eq!(hex::encode(sign_key.as_bytes()), "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793");
+        assert_eq!(hex::encode(vk.to_bytes()), "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a");
+        assert_eq!(address, Address { network: "lokichain".to_string(), vk });
+    }
+
+    #[test]
+    fn test_restore_accounts() {
+        let phrase = HdWallet::generate_mnemonic(24).unwrap();
+        assert_eq!(phrase.split(' ').count(), 24);
+        assert!(HdWallet::validate_mnemonic(&phrase).is_ok());
+
+        let wallet = HdWallet::from_mnemonic(&phrase, "").unwrap();
+        let restored = HdWallet::from_mnemonic(&phrase, "").unwrap();
+
+        let first = RawAddress::from_path("lokichain", "m/44'/1237'/0'/0'/0'").unwrap();
+        let second = RawAddress::from_path("lokichain", "m/44'/1237'/1'/0'/0'").unwrap();
+
+        let (_, _, address) = wallet.derive(&first);
+        assert_eq!(address, restored.derive(&first).2);
+        assert_ne!(address, wallet.derive(&second).2);
+        assert!(address.to_string().starts_with("lokichain1"));
+    }
+
+    #[test]
+    fn test_invalid_mnemonic() {
+        assert!(HdWallet::generate_mnemonic(13).is_err());
+        assert!(HdWallet::validate_mnemonic("abandon abandon").is_err());
+        // checksum mismatch
+        let phrase = MNEMONIC.replace("about", "abandon");
+        assert!(matches!(HdWallet::validate_mnemonic(&phrase), Err(DomainError::InvalidMnemonic(_))));
+    }
+}
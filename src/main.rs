@@ -1,8 +1,9 @@
 
 mod domain;
 mod application;
+mod infrastructure;
 
 fn main() {
 
 
-}
\ No newline at end of file
+}